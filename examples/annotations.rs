@@ -0,0 +1,81 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+/// An object, which has annotated members.
+#[derive(Handler)]
+#[interface(
+    "org.example.annotations",
+    // The annotation and the documentation of the interface, which are written into the
+    // introspection XML
+    annotation("org.freedesktop.DBus.Deprecated", "true"),
+    doc = "An example of annotations.",
+    // The annotations can be used with the positional and the named-key syntax
+    method(
+        "Reset",
+        reset,
+        "",
+        "",
+        annotation("org.freedesktop.DBus.Method.NoReply", "true"),
+        doc = "Set the counter to zero."
+    ),
+    method(
+        name = "Version",
+        handler = version,
+        returns = "s",
+        annotation(name = "org.example.Since", value = "1.1")
+    ),
+    property(
+        "Counter",
+        "u",
+        get_counter = "get",
+        doc = "The number of calls since the last reset.",
+        annotation("org.freedesktop.DBus.Property.EmitsChangedSignal", "false")
+    ),
+    signal("Overflow", "u", annotation("org.example.Unstable", "true"))
+)]
+struct AnnotationsObject {
+    counter: u32,
+}
+
+impl AnnotationsObject {
+    async fn reset(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        self.counter = 0;
+        Ok(())
+    }
+
+    async fn version(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        Ok(env!("CARGO_PKG_VERSION").to_string())
+    }
+
+    async fn get_counter(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<u32, (Error, String)> {
+        Ok(self.counter)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let annotations_object = AnnotationsObject { counter: 0 };
+    let object_path = "/org/example/annotations".try_into().unwrap();
+    annotations_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -7,11 +7,11 @@ use std::convert::TryInto;
 #[interface(
     "org.example.methods",
     method("MethodArray", method_array, "ai"),
-    method("MethodArrayReturn", method_array_return, "", "ai"),
+    method(name = "MethodArrayReturn", handler = method_array_return, returns = "ai"),
     method("MethodStruct", method_struct, "(isi)"),
-    method("MethodStructReturn", method_struct_return, "", "(isi)"),
+    method(name = "MethodStructReturn", handler = method_struct_return, returns = "(isi)"),
     method("MethodDict", method_dict, "a{yi}"),
-    method("MethodDictReturn", method_dict_return, "", "a{yi}")
+    method(name = "MethodDictReturn", handler = method_dict_return, returns = "a{yi}")
 )]
 struct MethodsObject {}
 
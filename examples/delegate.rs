@@ -0,0 +1,69 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+// The common interface, which is shared by multiple objects
+#[derive(Handler)]
+#[interface(
+    "org.example.Lifecycle",
+    method("Restart", restart),
+    property("Running", "b", get_running = "get")
+)]
+struct LifecycleImpl {
+    running: bool,
+}
+
+impl LifecycleImpl {
+    async fn restart(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn get_running(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<bool, (Error, String)> {
+        Ok(self.running)
+    }
+}
+
+#[derive(Handler)]
+#[interface("org.example.Device", method("Reset", reset))]
+struct DeviceObject {
+    // All calls to the interfaces of the field are routed to the field
+    #[delegate]
+    lifecycle: LifecycleImpl,
+}
+
+impl DeviceObject {
+    async fn reset(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        self.lifecycle.running = false;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let device_object = DeviceObject {
+        lifecycle: LifecycleImpl { running: true },
+    };
+    let object_path = "/org/example/device".try_into().unwrap();
+    device_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,79 @@
+use dbus_async::{Binder, DBus, DBusResult};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader, Value};
+use std::convert::TryInto;
+
+#[derive(Handler)]
+#[interface(
+    "org.example.fallback",
+    method("Method", method),
+    // All unknown members of this interface are handled by this function
+    fallback = member_fallback
+)]
+// All unknown interfaces are handled by this function. The compiler only allows literals as the
+// value of a struct attribute, so the function is given as a string here.
+#[fallback = "interface_fallback"]
+struct FallbackObject {}
+
+impl FallbackObject {
+    async fn method(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        // ...
+        Ok(())
+    }
+
+    async fn member_fallback(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        body: Vec<Value>,
+    ) -> DBusResult<()> {
+        // Forward the old name of the method
+        if let Some(member) = msg_header.get_member() {
+            if member == "OldMethod" && body.is_empty() {
+                if let Ok(msg) = msg_header.method_return() {
+                    dbus.send(msg)?;
+                }
+                return Ok(());
+            }
+        }
+        if let Some(msg) = msg_header.unknown_member() {
+            dbus.send(msg)?;
+        }
+        Ok(())
+    }
+
+    async fn interface_fallback(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        body: Vec<Value>,
+    ) -> DBusResult<()> {
+        println!(
+            "Unknown interface {:?} with {} values",
+            msg_header.get_interface(),
+            body.len()
+        );
+        if let Some(msg) = msg_header.unknown_interface() {
+            dbus.send(msg)?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let fallback_object = FallbackObject {};
+    let object_path = "/org/example/fallback".try_into().unwrap();
+    fallback_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,38 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use std::convert::TryInto;
+
+#[derive(Handler)]
+#[interface(
+    "org.example.field_properties",
+    // Read and write property, which is stored in the field `name`
+    // The DBus type `s` is inferred from the type of the field
+    property(name = "Name", field = name, emit_on_set = true),
+    // Read only property, which is stored in the field `count`
+    property(name = "Count", field = count, access = read),
+    // The DBus type can also be defined explicitly
+    property("Tags", "as", field = tags)
+)]
+struct FieldPropertiesObject {
+    name: String,
+    count: u32,
+    tags: Vec<String>,
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let field_properties_object = FieldPropertiesObject {
+        name: "Init value".to_string(),
+        count: 1,
+        tags: vec!["example".to_string()],
+    };
+    let object_path = "/org/example/field_properties".try_into().unwrap();
+    field_properties_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,79 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+/// The backend, which stores the values.
+trait Backend {
+    fn load(&self) -> String;
+
+    fn store(&mut self, value: String);
+}
+
+/// A backend, which keeps the value in memory.
+struct MemoryBackend {
+    value: String,
+}
+
+impl Backend for MemoryBackend {
+    fn load(&self) -> String {
+        self.value.clone()
+    }
+
+    fn store(&mut self, value: String) {
+        self.value = value;
+    }
+}
+
+#[derive(Handler)]
+#[interface(
+    "org.example.generic",
+    property("Value", "s", get_value = "get", set_value = "set")
+)]
+struct GenericObject<B>
+where
+    B: Backend,
+{
+    backend: B,
+}
+
+impl<B> GenericObject<B>
+where
+    B: Backend,
+{
+    async fn get_value(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        Ok(self.backend.load())
+    }
+
+    async fn set_value(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        new_value: String,
+    ) -> Result<(), (Error, String)> {
+        self.backend.store(new_value);
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let generic_object = GenericObject {
+        backend: MemoryBackend {
+            value: "Init value".to_string(),
+        },
+    };
+    let object_path = "/org/example/generic".try_into().unwrap();
+    generic_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,123 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::interface;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+struct ImplObject {
+    counter: u32,
+    name: String,
+}
+
+#[interface("org.example.impl")]
+// Annotations of the interface are defined below the `interface` attribute
+#[annotation("org.example.Version", "1")]
+/// An example of an interface, which is derived from an `impl` block.
+///
+/// The `///` comments are written into the introspection XML.
+impl ImplObject {
+    // The name of the method is `Increase` and the signature is `u` -> `u`
+    /// Increase the counter by `step` and return the new value.
+    #[method]
+    async fn increase(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        step: u32,
+    ) -> Result<u32, (Error, String)> {
+        self.counter += step;
+        Ok(self.counter)
+    }
+
+    // The name of the method is `Split` and the signature is `s` -> `as`
+    #[method("Split")]
+    async fn split_words(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        text: String,
+    ) -> Result<Vec<String>, (Error, String)> {
+        Ok(text.split_whitespace().map(|s| s.to_string()).collect())
+    }
+
+    // A tuple is returned as multiple output arguments: `su`
+    #[method]
+    async fn state(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(String, u32), (Error, String)> {
+        Ok((self.name.clone(), self.counter))
+    }
+
+    // The signature can be defined explicitly: a struct `(su)` instead of two arguments
+    #[method("StateStruct", "", "(su)")]
+    async fn state_struct(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(String, u32), (Error, String)> {
+        Ok((self.name.clone(), self.counter))
+    }
+
+    // `#[deprecated]` adds the `org.freedesktop.DBus.Deprecated` annotation to the method `Reset`
+    #[method(annotation("org.freedesktop.DBus.Method.NoReply", "true"))]
+    #[deprecated(note = "the counter should not be reset")]
+    async fn reset(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        self.counter = 0;
+        Ok(())
+    }
+
+    // The getter of the property `Name`
+    /// The name of the object.
+    #[get]
+    async fn get_name(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        Ok(self.name.clone())
+    }
+
+    // The setter of the property `Name`
+    #[set]
+    async fn set_name(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        name: String,
+    ) -> Result<(), (Error, String)> {
+        self.name = name;
+        Ok(())
+    }
+
+    // The read only property `Counter`
+    #[get("Counter")]
+    async fn counter(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<u32, (Error, String)> {
+        Ok(self.counter)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let impl_object = ImplObject {
+        counter: 0,
+        name: "Init value".to_string(),
+    };
+    let object_path = "/org/example/impl".try_into().unwrap();
+    impl_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::{interface_trait, Handler};
+use dbus_message_parser::{Error, MessageHeader, Value};
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+// The trait declares the handler functions of the interfaces of the introspection XML file.
+// The path of the file is relative to the directory of the `Cargo.toml`.
+#[interface_trait("examples/xml/org.example.notifications.xml")]
+trait Notifications {}
+
+// The interfaces of the object are defined by the same introspection XML file.
+#[derive(Handler)]
+#[interface_xml("examples/xml/org.example.notifications.xml")]
+struct NotificationServer {
+    notifications: HashMap<u32, String>,
+    next_id: u32,
+    do_not_disturb: bool,
+}
+
+#[async_trait]
+impl Notifications for NotificationServer {
+    async fn notify(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        app_name: String,
+        replaces_id: u32,
+        summary: String,
+        _body: String,
+        _hints: Vec<(String, Box<Value>)>,
+    ) -> Result<u32, (Error, String)> {
+        let id = if replaces_id == 0 {
+            self.next_id += 1;
+            self.next_id
+        } else {
+            replaces_id
+        };
+        if !self.do_not_disturb {
+            println!("{}: {}", app_name, summary);
+        }
+        self.notifications.insert(id, summary);
+        Ok(id)
+    }
+
+    async fn close_notification(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        id: u32,
+    ) -> Result<(), (Error, String)> {
+        if self.notifications.remove(&id).is_none() {
+            return Err((
+                "org.example.notifications.Error.UnknownId"
+                    .try_into()
+                    .unwrap(),
+                format!("The notification {} does not exist", id),
+            ));
+        }
+        // The emit functions of the signals and the properties are generated, too
+        let path = msg_header.get_path().unwrap();
+        let _ = self.emit_org_example_notifications_notification_closed(dbus, path, id, 2);
+        let _ = self.emit_org_example_notifications_count_changed(dbus, path);
+        Ok(())
+    }
+
+    async fn get_server_information(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(String, String, String), (Error, String)> {
+        Ok((
+            "interface_xml".to_string(),
+            "example".to_string(),
+            env!("CARGO_PKG_VERSION").to_string(),
+        ))
+    }
+
+    async fn get_do_not_disturb(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<bool, (Error, String)> {
+        Ok(self.do_not_disturb)
+    }
+
+    async fn set_do_not_disturb(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        value: bool,
+    ) -> Result<(), (Error, String)> {
+        self.do_not_disturb = value;
+        Ok(())
+    }
+
+    async fn get_count(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<u32, (Error, String)> {
+        Ok(self.notifications.len() as u32)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let notification_server = NotificationServer {
+        notifications: HashMap::new(),
+        next_id: 0,
+        do_not_disturb: false,
+    };
+    let object_path = "/org/example/notifications".try_into().unwrap();
+    notification_server
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -0,0 +1,35 @@
+use dbus_async::DBus;
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+
+#[derive(Handler)]
+// The XML of the interface is written into `$DBUS_ASYNC_DERIVE_XML_DIR`, if it is set
+#[dbus(export_xml = "org.example.introspection.xml")]
+#[interface(
+    "org.example.introspection",
+    method("Echo", echo, "s:text", "s:text"),
+    signal("Echoed", "s:text")
+)]
+struct IntrospectionObject {}
+
+impl IntrospectionObject {
+    async fn echo(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        text: String,
+    ) -> Result<String, (Error, String)> {
+        Ok(text)
+    }
+}
+
+fn main() {
+    // The introspection XML is available without a connection to the bus
+    println!("The XML of the interface:");
+    println!(
+        "{}",
+        IntrospectionObject::INTROSPECTION_XML_ORG_EXAMPLE_INTROSPECTION
+    );
+    println!("The XML of the object:");
+    println!("{}", IntrospectionObject::introspection_xml());
+}
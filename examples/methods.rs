@@ -9,7 +9,18 @@ use std::convert::TryInto;
     method("Method", method),
     method("MethodWithArgs", method_with_args, "su"),
     method("MethodWithReturnValue", method_with_return_value, "", "i"),
-    method("MethodWithArgsReturnValue", method_with_args_return_value, "n", "iv")
+    method("MethodWithArgsReturnValue", method_with_args_return_value, "n", "iv"),
+    // The arguments have names in the introspection XML
+    method("MethodWithNamedArgs", method_with_named_args, "s:text u:count", "s:result"),
+    method(
+        "MethodWithArgList",
+        method_with_arg_list,
+        args(
+            arg("text", "s", doc = "The text, which is repeated."),
+            arg("count", "u", annotation("org.example.Unit", "times"))
+        ),
+        returns(arg("result", "s"))
+    )
 )]
 struct MethodsObject {}
 
@@ -74,6 +85,27 @@ impl MethodsObject {
             Ok((20, Box::new(o)))
         }
     }
+
+    async fn method_with_named_args(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        text: String,
+        count: u32,
+    ) -> Result<String, (Error, String)> {
+        Ok(text.repeat(count as usize))
+    }
+
+    async fn method_with_arg_list(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        text: String,
+        count: u32,
+    ) -> Result<String, (Error, String)> {
+        self.method_with_named_args(dbus, msg_header, text, count)
+            .await
+    }
 }
 
 #[tokio::main]
@@ -0,0 +1,97 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader, ObjectPath};
+use futures::lock::Mutex;
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::Arc;
+
+#[derive(Handler)]
+#[interface("org.example.Device", property("Name", "s", get_name = "get"))]
+struct Device {
+    name: String,
+}
+
+impl Device {
+    async fn get_name(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        Ok(self.name.clone())
+    }
+}
+
+#[derive(Handler)]
+#[interface(
+    "org.example.DeviceManager",
+    method("AddDevice", add_device, "s"),
+    method("RemoveDevice", remove_device, "s")
+)]
+struct DeviceManager {
+    // The devices are the managed objects of the `org.freedesktop.DBus.ObjectManager` interface
+    #[object_manager]
+    devices: BTreeMap<ObjectPath, Arc<Mutex<Device>>>,
+}
+
+/// Get the object path of the device below the path of the manager.
+fn get_device_path(
+    msg_header: &MessageHeader,
+    name: &str,
+) -> Result<(ObjectPath, ObjectPath), (Error, String)> {
+    let manager_path = msg_header.get_path().unwrap().clone();
+    match ObjectPath::try_from(format!("{}/{}", manager_path, name)) {
+        Ok(device_path) => Ok((manager_path, device_path)),
+        Err(_) => Err((
+            "org.freedesktop.DBus.Error.InvalidArgs".try_into().unwrap(),
+            format!("invalid device name: {}", name),
+        )),
+    }
+}
+
+impl DeviceManager {
+    async fn add_device(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        name: String,
+    ) -> Result<(), (Error, String)> {
+        let (manager_path, device_path) = get_device_path(msg_header, &name)?;
+        let device = Arc::new(Mutex::new(Device { name }));
+        // Bind the device and emit the `InterfacesAdded` signal
+        let binding = self
+            .add_managed_object(dbus, &manager_path, device_path, device)
+            .await?;
+        tokio::spawn(binding);
+        Ok(())
+    }
+
+    async fn remove_device(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        name: String,
+    ) -> Result<(), (Error, String)> {
+        let (manager_path, device_path) = get_device_path(msg_header, &name)?;
+        // Unbind the device and emit the `InterfacesRemoved` signal
+        self.remove_managed_object(dbus, &manager_path, &device_path)
+            .await?;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let device_manager = DeviceManager {
+        devices: BTreeMap::new(),
+    };
+    let object_path = "/org/example/devices".try_into().unwrap();
+    device_manager
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
@@ -6,6 +6,10 @@ use std::convert::TryInto;
 #[derive(Handler)]
 #[interface(
     "org.example.properties",
+    // Emit the `PropertiesChanged` signal after every successful `Set` call
+    emit_on_set,
+    // Skip the properties, which cannot be read, in `GetAll` and log the error
+    get_all = log,
     // Read and write property of type string
     property(
         // The name of the property
@@ -17,14 +21,13 @@ use std::convert::TryInto;
         // The set function to change the value
         set_string_property = "set"
     ),
-    // Read only property of type dict<i32, String>
+    // Read only property of type dict<i32, String> defined by the named-key syntax
+    // The value never changes, so no `PropertiesChanged` signal is emitted for this property
     property(
-        // The name of the property
-        "DictProperty",
-        // The DBus type of the property
-        "a{is}",
-        // The get function to retrieve the current value
-        get_dict_property = "get",
+        name = "DictProperty",
+        type = "a{is}",
+        get = get_dict_property,
+        emits_changed = const
     ),
     // Write only property of type i32
     property(
@@ -44,9 +47,7 @@ struct PropertiesObject {
 
 impl PropertiesObject {
     fn new() -> PropertiesObject {
-        let mut dict_property = Vec::new();
-        dict_property.push((1, "TEST".to_string()));
-        dict_property.push((2, "EXAMPLE".to_string()));
+        let dict_property = vec![(1, "TEST".to_string()), (2, "EXAMPLE".to_string())];
 
         PropertiesObject {
             string_property: "Init value".to_string(),
@@ -62,7 +63,7 @@ impl PropertiesObject {
     ) -> Result<String, (Error, String)> {
         // The code of the get function of the StringProperty property
         // Only message which have a sender can access to this property
-        if let Some(_) = msg_header.get_sender() {
+        if msg_header.get_sender().is_some() {
             Ok(self.string_property.clone())
         } else {
             Err((
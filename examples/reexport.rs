@@ -0,0 +1,42 @@
+use dbus_async_derive::Handler;
+use facade::dbus_async::{Binder, DBus};
+use facade::dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+/// A facade crate, which re-exports the crates, which are needed by the generated code.
+/// In a real project, this would be a separate crate.
+mod facade {
+    pub use async_trait;
+    pub use dbus_async;
+    pub use dbus_message_parser;
+}
+
+#[derive(Handler)]
+#[dbus(crate = "crate::facade")]
+#[interface("org.example.reexport", method("Echo", echo, "s", "s"))]
+struct ReexportObject {}
+
+impl ReexportObject {
+    async fn echo(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        value: String,
+    ) -> Result<String, (Error, String)> {
+        Ok(value)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let reexport_object = ReexportObject {};
+    let object_path = "/org/example/reexport".try_into().unwrap();
+    reexport_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
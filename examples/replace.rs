@@ -0,0 +1,54 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+#[derive(Handler)]
+#[interface("org.example.replace", method("Method", method))]
+#[interface(
+    "org.freedesktop.DBus.Introspectable",
+    // Replace the generated implementation of the standard interface
+    replace,
+    method("Introspect", introspect, "", "s")
+)]
+struct ReplaceObject {}
+
+impl ReplaceObject {
+    async fn method(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        // ...
+        Ok(())
+    }
+
+    async fn introspect(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        let xml = r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node>
+  <interface name="org.example.replace">
+    <method name="Method"/>
+  </interface>
+</node>"#;
+        Ok(xml.to_string())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let replace_object = ReplaceObject {};
+    let object_path = "/org/example/replace".try_into().unwrap();
+    replace_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
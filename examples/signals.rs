@@ -0,0 +1,103 @@
+use dbus_async::{Binder, DBus};
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+use std::convert::TryInto;
+
+#[derive(Handler)]
+#[interface(
+    "org.example.signals",
+    method("SendMessage", send_message, "s"),
+    // The arguments of the signal are the arguments of the emit function
+    // The emit function is qualified by the interface:
+    // `emit_org_example_signals_message_received`
+    signal("MessageReceived", "s:message u:count"),
+    // The name of the emit function can be chosen by `emit_fn`
+    signal("Cleared", "", emit_fn = emit_cleared),
+    // The new value is sent by the `PropertiesChanged` signal
+    // The emit function is qualified by the interface: `emit_org_example_signals_counter_changed`
+    property("Counter", "u", get_counter = "get"),
+    // The `PropertiesChanged` signal only invalidates the property
+    property(
+        "LastMessage",
+        "s",
+        get_last_message = "get",
+        emits_changed = invalidates,
+        emit_fn = emit_last_message_changed
+    )
+)]
+struct SignalsObject {
+    counter: u32,
+    last_message: String,
+}
+
+fn to_dbus_error(e: dbus_async::DBusError) -> (Error, String) {
+    (
+        "org.freedesktop.DBus.Error.Failed".try_into().unwrap(),
+        e.to_string(),
+    )
+}
+
+impl SignalsObject {
+    async fn send_message(
+        &mut self,
+        dbus: &DBus,
+        msg_header: &MessageHeader,
+        message: String,
+    ) -> Result<(), (Error, String)> {
+        let path = msg_header.get_path().unwrap();
+        if message.is_empty() {
+            self.counter = 0;
+            // Emit the signal `Cleared`, which has no arguments
+            self.emit_cleared(dbus, path).map_err(to_dbus_error)?;
+        } else {
+            self.counter += 1;
+            // Emit the signal `MessageReceived` with the arguments `su`
+            self.emit_org_example_signals_message_received(
+                dbus,
+                path,
+                message.clone(),
+                self.counter,
+            )
+            .map_err(to_dbus_error)?;
+            self.last_message = message;
+            self.emit_last_message_changed(dbus, path)
+                .map_err(to_dbus_error)?;
+        }
+        // Emit the `PropertiesChanged` signal with the new value of `Counter`
+        self.emit_org_example_signals_counter_changed(dbus, path, self.counter)
+            .map_err(to_dbus_error)
+    }
+
+    async fn get_counter(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<u32, (Error, String)> {
+        Ok(self.counter)
+    }
+
+    async fn get_last_message(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<String, (Error, String)> {
+        Ok(self.last_message.clone())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let (dbus, _connection_join_handle) = DBus::session(true)
+        .await
+        .expect("failed to get the DBus object");
+
+    let signals_object = SignalsObject {
+        counter: 0,
+        last_message: String::new(),
+    };
+    let object_path = "/org/example/signals".try_into().unwrap();
+    signals_object
+        .bind(dbus, object_path)
+        .await
+        .expect("Something went wrong");
+}
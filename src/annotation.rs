@@ -0,0 +1,137 @@
+use crate::helper::{
+    get_ident_from_path, get_lit_str_from_lit, get_lit_str_from_option_nested_meta,
+    get_named_arguments, get_required_argument, is_named_syntax,
+};
+use crate::introspectable::escape_xml;
+use crate::name::{check_annotation_name, check_duplicates};
+use std::convert::TryFrom;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Error as SynError, LitStr, Meta, MetaList, NestedMeta, Result as SynResult};
+
+/// The name of the annotation, which marks a member or an interface as deprecated.
+pub(super) static DEPRECATED: &str = "org.freedesktop.DBus.Deprecated";
+
+/// An annotation of an interface, a method, a property or a signal (e.g.
+/// `annotation("org.freedesktop.DBus.Deprecated", "true")`).
+pub(crate) struct Annotation {
+    name: LitStr,
+    value: LitStr,
+}
+
+impl Annotation {
+    pub(super) fn new(name: LitStr, value: LitStr) -> Annotation {
+        Annotation { name, value }
+    }
+
+    pub(super) fn get_name(&self) -> &LitStr {
+        &self.name
+    }
+
+    pub(super) fn get_value(&self) -> &LitStr {
+        &self.value
+    }
+
+    /// Write the annotation element with the given indentation.
+    pub(super) fn to_introspect(&self, xml: &mut String, indent: &str) {
+        *xml += &format!(
+            "{}<annotation name=\"{}\" value=\"{}\"/>\n",
+            indent,
+            escape_xml(&self.name.value()),
+            escape_xml(&self.value.value())
+        );
+    }
+
+    /// Parse the named-key syntax: `annotation(name = "Name", value = "Value")`.
+    fn try_from_named(meta_list: &MetaList) -> SynResult<Annotation> {
+        let mut name = None;
+        let mut value = None;
+        for (key, lit) in get_named_arguments(meta_list, &["name", "value"])? {
+            match key.to_string().as_ref() {
+                "name" => name = Some(get_lit_str_from_lit(lit)?),
+                "value" => value = Some(get_lit_str_from_lit(lit)?),
+                _ => unreachable!(),
+            }
+        }
+
+        let name = get_required_argument(meta_list, "name", name)?;
+        check_annotation_name(&name)?;
+        let value = get_required_argument(meta_list, "value", value)?;
+        Ok(Annotation::new(name, value))
+    }
+}
+
+impl TryFrom<&MetaList> for Annotation {
+    type Error = SynError;
+
+    fn try_from(meta_list: &MetaList) -> Result<Self, Self::Error> {
+        // Get the ident and check if it is equal "annotation"
+        let meta_list_type = get_ident_from_path(&meta_list.path)?;
+        if meta_list_type != "annotation" {
+            return Err(SynError::new(
+                meta_list_type.span(),
+                "excepted \"annotation\"",
+            ));
+        }
+
+        if is_named_syntax(meta_list) {
+            return Annotation::try_from_named(meta_list);
+        }
+
+        let nested_iter = &mut meta_list.nested.iter();
+
+        // Get the name of the annotation
+        let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        check_annotation_name(&name)?;
+
+        // Get the value of the annotation
+        let value = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+
+        if nested_iter.next().is_some() {
+            return Err(SynError::new(meta_list.span(), "too many arguments"));
+        }
+        Ok(Annotation::new(name, value))
+    }
+}
+
+/// Check if a nested meta is an `annotation(...)` entry.
+fn is_annotation(nested_meta: &NestedMeta) -> bool {
+    matches!(
+        nested_meta,
+        NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident("annotation")
+    )
+}
+
+/// Split the `annotation(...)` entries from the other entries of a `MetaList`.
+/// The returned `MetaList` contains all other entries in the same order.
+pub(super) fn split_annotations(meta_list: &MetaList) -> SynResult<(MetaList, Vec<Annotation>)> {
+    let mut nested = Punctuated::new();
+    let mut annotations = Vec::new();
+    for nested_meta in &meta_list.nested {
+        match nested_meta {
+            NestedMeta::Meta(Meta::List(annotation)) if is_annotation(nested_meta) => {
+                annotations.push(Annotation::try_from(annotation)?);
+            }
+            nested_meta => nested.push(nested_meta.clone()),
+        }
+    }
+    check_duplicates(annotations.iter().map(Annotation::get_name), "annotation")?;
+
+    let meta_list = MetaList {
+        path: meta_list.path.clone(),
+        paren_token: meta_list.paren_token,
+        nested,
+    };
+    Ok((meta_list, annotations))
+}
+
+/// Write all annotations with the given indentation.
+pub(super) fn annotations_to_introspect(
+    annotations: &[Annotation],
+    xml: &mut String,
+    indent: &str,
+) {
+    for annotation in annotations {
+        annotation.to_introspect(xml, indent);
+    }
+}
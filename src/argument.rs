@@ -0,0 +1,428 @@
+use crate::annotation::{annotations_to_introspect, split_annotations, Annotation};
+use crate::doc::{doc_to_introspect, split_doc};
+use crate::helper::{get_ident_from_path, get_lit_str_from_option_nested_meta, to_snake_case};
+use crate::introspectable::escape_xml;
+use crate::signature::SignatureIterator;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::format_ident;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Error as SynError, Lit, LitStr, Meta, MetaList, NestedMeta, Result as SynResult};
+
+/// The name, the documentation and the annotations of an argument of a method or a signal.
+/// The signature of the argument is stored separately.
+#[derive(Default)]
+pub(crate) struct Argument {
+    name: Option<Ident>,
+    /// The name in the introspection XML, if it differs from the identifier (e.g. `appName`).
+    xml_name: Option<String>,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
+}
+
+impl Argument {
+    pub(super) fn new(name: Ident) -> Argument {
+        Argument {
+            name: Some(name),
+            xml_name: None,
+            annotations: Vec::new(),
+            doc: None,
+        }
+    }
+
+    /// Create an argument of the introspection XML. The identifier is the name in snake case.
+    pub(super) fn from_xml(
+        name: &str,
+        span: Span,
+        annotations: Vec<Annotation>,
+        doc: Option<LitStr>,
+    ) -> SynResult<Argument> {
+        let ident = get_ident_from_xml_name(name, span, "argument")?;
+        let xml_name = if ident.unraw() == name {
+            None
+        } else {
+            Some(name.to_string())
+        };
+        Ok(Argument {
+            name: Some(ident),
+            xml_name,
+            annotations,
+            doc,
+        })
+    }
+
+    /// Create an argument of the introspection XML without a name.
+    pub(super) fn from_xml_unnamed(annotations: Vec<Annotation>, doc: Option<LitStr>) -> Argument {
+        Argument {
+            name: None,
+            xml_name: None,
+            annotations,
+            doc,
+        }
+    }
+
+    /// Set the name of the argument, if it has no name.
+    pub(super) fn set_default_name(&mut self, name: Option<Ident>) {
+        if self.name.is_none() {
+            self.name = name;
+        }
+    }
+
+    /// Get the name of the argument in the introspection XML. If the argument has no name, then
+    /// the default name `arg_<index>` is returned.
+    pub(super) fn get_name(&self, index: usize) -> String {
+        if let Some(xml_name) = &self.xml_name {
+            return xml_name.clone();
+        }
+        match &self.name {
+            Some(name) => name.unraw().to_string(),
+            None => format!("arg_{}", index),
+        }
+    }
+
+    /// Get the name of the argument in the introspection XML, if it has a name.
+    pub(super) fn get_name_if_named(&self) -> Option<String> {
+        if let Some(xml_name) = &self.xml_name {
+            return Some(xml_name.clone());
+        }
+        self.name.as_ref().map(|name| name.unraw().to_string())
+    }
+
+    /// Get the identifier of the variable in the generated code. If the argument has no name,
+    /// then the default name `arg_<index>` is returned.
+    pub(super) fn get_ident(&self, index: usize, span: Span) -> Ident {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format_ident!("arg_{}", index, span = span),
+        }
+    }
+
+    /// Write the argument element. The name is only written if it is given.
+    pub(super) fn to_introspect(
+        &self,
+        xml: &mut String,
+        signature: &str,
+        name: Option<&str>,
+        direction: Option<&str>,
+    ) {
+        doc_to_introspect(self.doc.as_ref(), xml, "      ");
+        *xml += &format!("      <arg type=\"{}\"", signature);
+        if let Some(name) = name {
+            *xml += &format!(" name=\"{}\"", escape_xml(name));
+        }
+        if let Some(direction) = direction {
+            *xml += &format!(" direction=\"{}\"", direction);
+        }
+        if self.annotations.is_empty() {
+            *xml += "/>\n";
+        } else {
+            *xml += ">\n";
+            annotations_to_introspect(&self.annotations, xml, "        ");
+            *xml += "      </arg>\n";
+        }
+    }
+}
+
+/// The signatures and the corresponding arguments.
+pub(super) type Arguments = (
+    Vec<(String, TokenStream, TokenStream, TokenStream)>,
+    Vec<Argument>,
+);
+
+/// Parse the name of an argument, which has to be a Rust identifier.
+fn get_argument_name(name: &str, span: Span) -> SynResult<Ident> {
+    match syn::parse_str::<Ident>(name) {
+        Ok(mut ident) => {
+            ident.set_span(span);
+            Ok(ident)
+        }
+        Err(_) => Err(SynError::new(
+            span,
+            format!(
+                "invalid argument name {:?}: it must be a Rust identifier",
+                name
+            ),
+        )),
+    }
+}
+
+/// Get the identifier of a name of the introspection XML (e.g. `AppName` -> `app_name`). A raw
+/// identifier is used for a keyword (e.g. `type` -> `r#type`).
+pub(super) fn get_ident_from_xml_name(name: &str, span: Span, kind: &str) -> SynResult<Ident> {
+    let snake_case = to_snake_case(name);
+    let ident = syn::parse_str::<Ident>(&snake_case)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{}", snake_case)));
+    match ident {
+        Ok(mut ident) => {
+            ident.set_span(span);
+            Ok(ident)
+        }
+        Err(_) => Err(SynError::new(
+            span,
+            format!(
+                "invalid {} name {:?}: it cannot be converted into a Rust identifier",
+                kind, name
+            ),
+        )),
+    }
+}
+
+/// Parse a single complete type of a named argument.
+fn get_argument_signature(
+    signature: &LitStr,
+) -> SynResult<(String, TokenStream, TokenStream, TokenStream)> {
+    let mut signatures: Vec<_> = SignatureIterator::from(signature).collect::<SynResult<_>>()?;
+    if signatures.len() != 1 {
+        return Err(SynError::new(
+            signature.span(),
+            "excepted only one signature type for a named argument",
+        ));
+    }
+    Ok(signatures.remove(0))
+}
+
+/// Parse the arguments of a string literal. The arguments are separated by whitespaces and each
+/// argument can have a name after a colon (e.g. `"s:name u:count"`). An argument without a name
+/// can contain multiple types (e.g. `"su"`).
+pub(super) fn get_arguments_from_lit_str(lit_str: &LitStr) -> SynResult<Arguments> {
+    let value = lit_str.value();
+    let span = lit_str.span();
+    let mut signatures = Vec::new();
+    let mut arguments = Vec::new();
+    if !value.contains(':') && !value.contains(char::is_whitespace) {
+        for signature in SignatureIterator::from(lit_str) {
+            signatures.push(signature?);
+            arguments.push(Argument::default());
+        }
+        return Ok((signatures, arguments));
+    }
+
+    for token in value.split_whitespace() {
+        if let Some((signature, name)) = token.split_once(':') {
+            let signature = get_argument_signature(&LitStr::new(signature, span))?;
+            signatures.push(signature);
+            arguments.push(Argument::new(get_argument_name(name, span)?));
+        } else {
+            for signature in SignatureIterator::from(&LitStr::new(token, span)) {
+                signatures.push(signature?);
+                arguments.push(Argument::default());
+            }
+        }
+    }
+    Ok((signatures, arguments))
+}
+
+/// Parse a single argument: `arg("name", "signature")`. The argument can have a documentation
+/// (`doc = "..."`) and annotations.
+fn get_argument_from_meta_list(
+    meta_list: &MetaList,
+) -> SynResult<((String, TokenStream, TokenStream, TokenStream), Argument)> {
+    let meta_list_type = get_ident_from_path(&meta_list.path)?;
+    if meta_list_type != "arg" {
+        return Err(SynError::new(meta_list_type.span(), "excepted \"arg\""));
+    }
+    let (meta_list, annotations) = split_annotations(meta_list)?;
+    let (meta_list, doc) = split_doc(&meta_list)?;
+    let nested_iter = &mut meta_list.nested.iter();
+
+    // Get the name of the argument
+    let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+    let name = get_argument_name(&name.value(), name.span())?;
+
+    // Get the signature of the argument
+    let signature = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+    let signature = get_argument_signature(&signature)?;
+
+    if nested_iter.next().is_some() {
+        return Err(SynError::new(meta_list.span(), "too many arguments"));
+    }
+    let argument = Argument {
+        name: Some(name),
+        xml_name: None,
+        annotations,
+        doc,
+    };
+    Ok((signature, argument))
+}
+
+/// Parse the arguments of a string literal (see [`get_arguments_from_lit_str`]) or of a list of
+/// `arg(...)` entries (e.g. `args(arg("name", "s"), arg("count", "u"))`).
+/// The list has to have the name `list_name`.
+pub(super) fn get_arguments_from_nested_meta(
+    nested_meta: &NestedMeta,
+    list_name: &str,
+) -> SynResult<Arguments> {
+    match nested_meta {
+        NestedMeta::Lit(Lit::Str(lit_str)) => get_arguments_from_lit_str(lit_str),
+        NestedMeta::Meta(Meta::List(meta_list)) if meta_list.path.is_ident(list_name) => {
+            let mut signatures = Vec::new();
+            let mut arguments = Vec::new();
+            for nested_meta in &meta_list.nested {
+                if let NestedMeta::Meta(Meta::List(meta_list)) = nested_meta {
+                    let (signature, argument) = get_argument_from_meta_list(meta_list)?;
+                    signatures.push(signature);
+                    arguments.push(argument);
+                } else {
+                    return Err(SynError::new(nested_meta.span(), "excepted \"arg\""));
+                }
+            }
+            Ok((signatures, arguments))
+        }
+        nested_meta => Err(SynError::new(
+            nested_meta.span(),
+            format!("excepted a signature str or {}(...)", list_name),
+        )),
+    }
+}
+
+/// Check if an argument name is defined multiple times or if it is used by the generated code
+/// (`reserved`). The default names (`arg_<index>`) are checked, too. The index of the first
+/// argument is `first_index`.
+pub(super) fn check_argument_names(
+    arguments: &[Argument],
+    first_index: usize,
+    reserved: &[&str],
+) -> SynResult<()> {
+    let names: Vec<String> = arguments
+        .iter()
+        .enumerate()
+        .map(|(i, argument)| argument.get_name(first_index + i))
+        .collect();
+    for (i, argument) in arguments.iter().enumerate() {
+        // The default names cannot collide with each other
+        let name = if let Some(name) = &argument.name {
+            name
+        } else {
+            continue;
+        };
+        let value = &names[i];
+        if reserved.contains(&name.unraw().to_string().as_str()) {
+            return Err(SynError::new(
+                name.span(),
+                format!(
+                    "the argument name {:?} is used by the generated code",
+                    name.unraw().to_string()
+                ),
+            ));
+        }
+        if names
+            .iter()
+            .enumerate()
+            .any(|(j, other)| i != j && value == other)
+        {
+            return Err(SynError::new(
+                name.span(),
+                format!("argument {:?} is defined multiple times", value),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_argument_names, get_arguments_from_lit_str, get_ident_from_xml_name};
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    /// Get the signatures and the names in the introspection XML.
+    fn arguments(value: &str) -> Result<Vec<(String, String)>, String> {
+        let lit_str = LitStr::new(value, Span::call_site());
+        let (signatures, arguments) =
+            get_arguments_from_lit_str(&lit_str).map_err(|e| e.to_string())?;
+        Ok(signatures
+            .into_iter()
+            .zip(arguments.iter().enumerate())
+            .map(|((signature, _, _, _), (i, argument))| (signature, argument.get_name(i)))
+            .collect())
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(signature, name)| (signature.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn unnamed_arguments() {
+        assert_eq!(
+            arguments("sa{sv}").unwrap(),
+            pairs(&[("s", "arg_0"), ("a{sv}", "arg_1")])
+        );
+    }
+
+    #[test]
+    fn named_arguments() {
+        assert_eq!(
+            arguments("s:name u:count").unwrap(),
+            pairs(&[("s", "name"), ("u", "count")])
+        );
+        assert_eq!(
+            arguments("a{sv}:hints su (ii):position").unwrap(),
+            pairs(&[
+                ("a{sv}", "hints"),
+                ("s", "arg_1"),
+                ("u", "arg_2"),
+                ("(ii)", "position")
+            ])
+        );
+        assert_eq!(arguments("s:r#type").unwrap(), pairs(&[("s", "type")]));
+    }
+
+    #[test]
+    fn invalid_named_arguments() {
+        assert_eq!(
+            arguments("su:name").unwrap_err(),
+            "excepted only one signature type for a named argument"
+        );
+        assert_eq!(
+            arguments("s:1name").unwrap_err(),
+            "invalid argument name \"1name\": it must be a Rust identifier"
+        );
+        assert_eq!(
+            arguments("s:my-name").unwrap_err(),
+            "invalid argument name \"my-name\": it must be a Rust identifier"
+        );
+    }
+
+    #[test]
+    fn argument_names() {
+        let check = |value: &str, first_index: usize| {
+            let lit_str = LitStr::new(value, Span::call_site());
+            let (_, arguments) = get_arguments_from_lit_str(&lit_str).unwrap();
+            check_argument_names(&arguments, first_index, &["dbus"]).map_err(|e| e.to_string())
+        };
+        assert!(check("s:name u:count", 0).is_ok());
+        assert!(check("su", 0).is_ok());
+        assert_eq!(
+            check("s:name u:name", 0).unwrap_err(),
+            "argument \"name\" is defined multiple times"
+        );
+        assert_eq!(
+            check("s:arg_1 u", 0).unwrap_err(),
+            "argument \"arg_1\" is defined multiple times"
+        );
+        assert!(check("s:arg_1 u", 1).is_ok());
+        assert_eq!(
+            check("s:dbus", 0).unwrap_err(),
+            "the argument name \"dbus\" is used by the generated code"
+        );
+    }
+
+    #[test]
+    fn xml_names() {
+        let ident = |name: &str| {
+            get_ident_from_xml_name(name, Span::call_site(), "argument")
+                .map(|ident| ident.to_string())
+                .map_err(|e| e.to_string())
+        };
+        assert_eq!(ident("appName").unwrap(), "app_name");
+        assert_eq!(ident("AppName").unwrap(), "app_name");
+        assert_eq!(ident("type").unwrap(), "r#type");
+        assert_eq!(
+            ident("app-name").unwrap_err(),
+            "invalid argument name \"app-name\": it cannot be converted into a Rust identifier"
+        );
+    }
+}
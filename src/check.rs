@@ -0,0 +1,100 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote_spanned};
+
+/// Convert a Rust type into a readable string for the error messages.
+/// The paths of the generated types are shortened (`::std::vec::Vec` -> `Vec`).
+pub(super) fn rust_type_to_string(rust_type: &TokenStream) -> String {
+    rust_type
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace("::std::string::", "")
+        .replace("::std::vec::", "")
+        .replace("::std::boxed::", "")
+        .replace("__dbus_message_parser::", "")
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" ,", ",")
+        .replace(",)", ")")
+}
+
+/// Get the name of the function, which checks the argument `i` of the function `function`.
+/// The function converts a value of the type of the signature into the type, which is inferred
+/// by the usage of the function. Because the function is generic, a wrong type only causes the
+/// error of the check and not a mismatched types error.
+pub(super) fn get_argument_check_function(function: &Ident, i: usize, span: Span) -> Ident {
+    format_ident!("__dbus_check_{}_argument_{}", function, i, span = span)
+}
+
+/// Get the name of the function, which checks the return value of the function `function`.
+/// The function converts the result into `Result<T, (Error, String)>`, where `T` is the type of
+/// the signature.
+pub(super) fn get_output_check_function(function: &Ident, span: Span) -> Ident {
+    format_ident!("__dbus_check_{}_output", function, span = span)
+}
+
+/// Create a check, which only accepts the type `rust_type` for the argument `i` of the function
+/// `function`.
+/// If another type is used then the error message `message` is shown, where `{Self}` is replaced
+/// by the type, which is used.
+/// It returns the name of the check function and the items of the check.
+pub(super) fn create_argument_check(
+    function: &Ident,
+    i: usize,
+    rust_type: &TokenStream,
+    message: &str,
+    span: Span,
+) -> (Ident, TokenStream) {
+    let check_trait = format_ident!("__DBusCheck_{}_Argument{}", function, i, span = span);
+    let check_function = get_argument_check_function(function, i, span);
+    let items = quote_spanned! {span=>
+        #[allow(non_camel_case_types)]
+        #[diagnostic::on_unimplemented(message = #message)]
+        trait #check_trait {
+            fn from_value(value: #rust_type) -> Self;
+        }
+        impl #check_trait for #rust_type {
+            fn from_value(value: #rust_type) -> Self {
+                value
+            }
+        }
+        fn #check_function<T: #check_trait>(value: #rust_type) -> T {
+            T::from_value(value)
+        }
+    };
+    (check_function, items)
+}
+
+/// Create a check, which only accepts `Result<rust_type, (Error, String)>` as the return value of
+/// the function `function`.
+/// It returns the name of the check function and the items of the check.
+pub(super) fn create_output_check(
+    function: &Ident,
+    rust_type: &TokenStream,
+    message: &str,
+    span: Span,
+) -> (Ident, TokenStream) {
+    let check_trait = format_ident!("__DBusCheck_{}_Output", function, span = span);
+    let check_function = get_output_check_function(function, span);
+    let items = quote_spanned! {span=>
+        #[allow(non_camel_case_types)]
+        #[diagnostic::on_unimplemented(message = #message)]
+        trait #check_trait {
+            fn into_value(self) -> #rust_type;
+        }
+        impl #check_trait for #rust_type {
+            fn into_value(self) -> #rust_type {
+                self
+            }
+        }
+        fn #check_function<T: #check_trait>(
+            result: ::std::result::Result<T, (__dbus_message_parser::Error, ::std::string::String)>,
+        ) -> ::std::result::Result<#rust_type, (__dbus_message_parser::Error, ::std::string::String)> {
+            result.map(#check_trait::into_value)
+        }
+    };
+    (check_function, items)
+}
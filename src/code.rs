@@ -6,8 +6,8 @@ pub(super) fn check_result() -> TokenStream {
     quote! {
         {
             match result {
-                Ok(r) => r,
-                Err((name, message)) => {
+                ::std::result::Result::Ok(r) => r,
+                ::std::result::Result::Err((name, message)) => {
                     let msg = header.error(name, message);
                     return dbus.send(msg);
                 }
@@ -39,7 +39,7 @@ pub(super) fn invalid_args_signature(excepted: &str) -> TokenStream {
     );
     let invalid_args = invalid_args();
     quote! {
-        let text = format!(#text, signature);
+        let text = ::std::format!(#text, signature);
         #invalid_args
     }
 }
@@ -61,7 +61,7 @@ pub(super) fn check_signature_from_header(excepted: &str) -> TokenStream {
     let check_signature = check_signature(excepted);
     let missing_value = missing_value(excepted);
     quote! {
-        if let Some(signature) = header.get_signature() {
+        if let ::std::option::Option::Some(signature) = header.get_signature() {
             #check_signature
         } else {
             #missing_value
@@ -72,9 +72,9 @@ pub(super) fn check_signature_from_header(excepted: &str) -> TokenStream {
 pub(super) fn check_if_no_signature_from_header() -> TokenStream {
     let invalid_args = invalid_args();
     quote! {
-        if let Some(signature) = header.get_signature() {
+        if let ::std::option::Option::Some(signature) = header.get_signature() {
             if !signature.is_empty() {
-                let text = format!("too many arguments: got {}", signature);
+                let text = ::std::format!("too many arguments: got {}", signature);
                 #invalid_args
             }
         }
@@ -84,8 +84,8 @@ pub(super) fn check_if_no_signature_from_header() -> TokenStream {
 pub(super) fn create_return_msg_from_header() -> TokenStream {
     quote! {
         match header.method_return() {
-            Ok(msg) => msg,
-            Err(msg) => return dbus.send(msg),
+            ::std::result::Result::Ok(msg) => msg,
+            ::std::result::Result::Err(msg) => return dbus.send(msg),
         }
     }
 }
@@ -93,70 +93,116 @@ pub(super) fn create_return_msg_from_header() -> TokenStream {
 pub(super) fn check_if_no_value_from_body_iter() -> TokenStream {
     let invalid_args = invalid_args();
     quote! {
-        if let Some(v) = body_iter.next() {
-            let mut signature = String::new();
+        if let ::std::option::Option::Some(v) = body_iter.next() {
+            let mut signature = ::std::string::String::new();
             v.get_signature(&mut signature);
-            let text = format!("Too many values: got {}", signature);
+            let text = ::std::format!("Too many values: got {}", signature);
             #invalid_args
         }
     }
 }
 
-pub(super) fn unknown_interface_from_header() -> TokenStream {
+/// Create the code, which sends the standard error `name` with the message `text` to the caller.
+pub(super) fn create_error_code(name: &str, text: TokenStream) -> TokenStream {
+    let name = format!("org.freedesktop.DBus.Error.{}", name);
     quote! {
         {
-            if let Some(msg) = header.unknown_interface() {
-                dbus.send(msg)?;
-            }
-            return Ok(());
+            let msg = header.error(
+                ::std::convert::TryFrom::try_from(#name.to_string()).unwrap(),
+                #text,
+            );
+            return dbus.send(msg);
         }
     }
 }
 
-pub(super) fn unknown_member_from_header() -> TokenStream {
+pub(super) fn unknown_interface_from_header() -> TokenStream {
+    let error = create_error_code(
+        "UnknownInterface",
+        quote! { ::std::format!("The object does not have the interface `{}`", interface) },
+    );
     quote! {
         {
-            if let Some(msg) = header.unknown_member() {
-                dbus.send(msg)?;
+            if let ::std::option::Option::Some(interface) = header.get_interface() {
+                #error
             }
-            return Ok(())
+            return ::std::result::Result::Ok(());
         }
     }
 }
 
-pub(super) fn unknown_property_from_header() -> TokenStream {
+/// Create the code, which sends an error, because the object does not have the interface, which
+/// is given in the body (e.g. `org.freedesktop.DBus.Properties.Get`).
+pub(super) fn unknown_interface_from_body() -> TokenStream {
+    create_error_code(
+        "UnknownInterface",
+        quote! { ::std::format!("The object does not have the interface `{}`", interface) },
+    )
+}
+
+pub(super) fn unknown_member_from_header() -> TokenStream {
+    let error = create_error_code(
+        "UnknownMethod",
+        quote! {
+            match header.get_interface() {
+                ::std::option::Option::Some(interface) => ::std::format!(
+                    "The interface `{}` does not have the method `{}`",
+                    interface,
+                    member
+                ),
+                ::std::option::Option::None => {
+                    ::std::format!("The object does not have the method `{}`", member)
+                }
+            }
+        },
+    );
     quote! {
         {
-            let msg = header.unknown_property(property);
-            return dbus.send(msg);
+            if let ::std::option::Option::Some(member) = header.get_member() {
+                #error
+            }
+            return ::std::result::Result::Ok(())
         }
     }
 }
 
+pub(super) fn unknown_property_from_header() -> TokenStream {
+    create_error_code(
+        "UnknownProperty",
+        quote! {
+            ::std::format!(
+                "The interface `{}` does not have the property `{}`",
+                interface,
+                property
+            )
+        },
+    )
+}
+
 pub(super) fn get_interface_from_header() -> TokenStream {
+    let error = create_error_code(
+        "UnknownInterface",
+        quote! { "The message does not have an interface".to_string() },
+    );
     quote! {
-        if let Some(interface) = header.get_interface() {
+        if let ::std::option::Option::Some(interface) = header.get_interface() {
             interface.as_ref()
         } else {
-            let msg = header.error(
-                std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.Interface".to_string()).unwrap(),
-                "Message does not have a interface".to_string(),
-            );
-            return dbus.send(msg);
+            #error
         }
     }
 }
 
 pub(super) fn get_member_from_header() -> TokenStream {
+    let error = create_error_code(
+        "UnknownMethod",
+        quote! { "The message does not have a member".to_string() },
+    );
     quote! {
-        if let Some(member) = header.get_member() {
+        if let ::std::option::Option::Some(member) = header.get_member() {
             member.as_ref()
         } else {
-            let msg = header.error(
-                std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.Member".to_string()).unwrap(),
-                "Message does not have a member".to_string(),
-            );
-            return dbus.send(msg);
+            #error
         }
     }
 }
@@ -169,7 +215,7 @@ pub(super) fn get_value_from_body_iter(
 ) -> TokenStream {
     let missing_value = missing_value(signature);
     quote! {
-        let #name: #rust_type = if let Some(i) = body_iter.next() {
+        let #name: #rust_type = if let ::std::option::Option::Some(i) = body_iter.next() {
             #value_to_rust
         } else {
             #missing_value
@@ -179,13 +225,13 @@ pub(super) fn get_value_from_body_iter(
 
 pub(super) fn get_string_from_body_iter(name: &Ident) -> TokenStream {
     let invalid_args_signature = invalid_args_signature("s");
-    let rust_type = "String".parse().unwrap();
+    let rust_type = "::std::string::String".parse().unwrap();
     let value_to_rust = quote! {
         {
-            if let dbus_message_parser::Value::String(s) = i {
+            if let __dbus_message_parser::Value::String(s) = i {
                 s
             } else {
-                let mut signature = String::new();
+                let mut signature = ::std::string::String::new();
                 i.get_signature(&mut signature);
                 #invalid_args_signature;
             }
@@ -196,15 +242,15 @@ pub(super) fn get_string_from_body_iter(name: &Ident) -> TokenStream {
 
 pub(super) fn get_variant_from_body_iter(name: &Ident) -> TokenStream {
     let invalid_args_signature = invalid_args_signature("v");
-    let rust_type = "std::boxed::Box<dbus_message_parser::Value>"
+    let rust_type = "::std::boxed::Box<__dbus_message_parser::Value>"
         .parse()
         .unwrap();
     let value_to_rust = quote! {
         {
-            if let dbus_message_parser::Value::Variant(s) = i {
+            if let __dbus_message_parser::Value::Variant(s) = i {
                 s
             } else {
-                let mut signature = String::new();
+                let mut signature = ::std::string::String::new();
                 i.get_signature(&mut signature);
                 #invalid_args_signature;
             }
@@ -217,7 +263,7 @@ pub(super) fn default_case_wrong_case(excepted: &str) -> TokenStream {
     let invalid_args_signature = invalid_args_signature(excepted);
     quote! {
         v => {
-            let mut signature = String::new();
+            let mut signature = ::std::string::String::new();
             v.get_signature(&mut signature);
             #invalid_args_signature
         }
@@ -228,7 +274,7 @@ pub(super) fn create_value_to_rust(enum_type: &TokenStream, excepted: &str) -> T
     let default_case_wrong_case = default_case_wrong_case(excepted);
     quote! {
         match i {
-            dbus_message_parser::Value::#enum_type(i) => i,
+            __dbus_message_parser::Value::#enum_type(i) => i,
             #default_case_wrong_case
         }
     }
@@ -236,6 +282,6 @@ pub(super) fn create_value_to_rust(enum_type: &TokenStream, excepted: &str) -> T
 
 pub(super) fn create_rust_to_value(enum_type: &TokenStream) -> TokenStream {
     quote! {
-        dbus_message_parser::Value::#enum_type(i)
+        __dbus_message_parser::Value::#enum_type(i)
     }
 }
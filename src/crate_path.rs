@@ -0,0 +1,100 @@
+use crate::export::ExportXml;
+use crate::helper::{get_ident_from_path, get_lit_str_from_lit, get_named_arguments};
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::quote;
+use syn::{Error as SynError, MetaList, Path, Result as SynResult};
+
+/// Create the aliases of the crates, which are used by the generated code.
+/// By default the crates are direct dependencies. If a crate path is given then the crates are
+/// re-exported by this crate. The `log` crate is only needed, if `uses_log` is true and the
+/// `futures` crate is only needed, if `uses_futures` is true.
+pub(super) fn create_crate_aliases_code(
+    crate_path: Option<&Path>,
+    uses_log: bool,
+    uses_futures: bool,
+) -> TokenStream {
+    let prefix = if let Some(crate_path) = crate_path {
+        quote! { #crate_path }
+    } else {
+        TokenStream::new()
+    };
+    let log = if uses_log {
+        quote! { use #prefix::log as __log; }
+    } else {
+        TokenStream::new()
+    };
+    let futures = if uses_futures {
+        quote! { use #prefix::futures as __futures; }
+    } else {
+        TokenStream::new()
+    };
+    quote! {
+        use #prefix::async_trait as __async_trait;
+        use #prefix::dbus_async as __dbus_async;
+        use #prefix::dbus_message_parser as __dbus_message_parser;
+        #log
+        #futures
+    }
+}
+
+/// Replace the aliases of the crates by the paths of the crates. This is used for the code
+/// outside of the generated `const` block, where the aliases are not defined.
+pub(super) fn replace_crate_aliases(code: TokenStream, crate_path: Option<&Path>) -> TokenStream {
+    let mut result = TokenStream::new();
+    for token_tree in code {
+        match token_tree {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                if let Some(name) = name.strip_prefix("__") {
+                    if ["async_trait", "dbus_async", "dbus_message_parser"].contains(&name) {
+                        let name = Ident::new(name, ident.span());
+                        let code = if let Some(crate_path) = crate_path {
+                            quote! { #crate_path::#name }
+                        } else {
+                            quote! { ::#name }
+                        };
+                        result.extend(code);
+                        continue;
+                    }
+                }
+                result.extend(Some(TokenTree::Ident(ident)));
+            }
+            TokenTree::Group(group) => {
+                let stream = replace_crate_aliases(group.stream(), crate_path);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                result.extend(Some(TokenTree::Group(replaced)));
+            }
+            token_tree => result.extend(Some(token_tree)),
+        }
+    }
+    result
+}
+
+/// Parse `#[dbus(crate = "path", export_xml = true)]`. Both keys are optional, but at least one
+/// key has to be defined.
+pub(super) fn parse_dbus(meta_list: &MetaList) -> SynResult<(Option<Path>, Option<ExportXml>)> {
+    let meta_list_type = get_ident_from_path(&meta_list.path)?;
+    if meta_list_type != "dbus" {
+        return Err(SynError::new(meta_list_type.span(), "excepted \"dbus\""));
+    }
+
+    let arguments = get_named_arguments(meta_list, &["crate", "export_xml"])?;
+    if arguments.is_empty() {
+        return Err(SynError::new(
+            meta_list_type.span(),
+            "no \"crate\" or \"export_xml\" defined",
+        ));
+    }
+
+    let mut crate_path = None;
+    let mut export_xml = None;
+    for (key, lit) in arguments {
+        match key.to_string().as_ref() {
+            "crate" => crate_path = Some(get_lit_str_from_lit(lit)?.parse()?),
+            "export_xml" => export_xml = ExportXml::try_from_lit(lit)?,
+            _ => unreachable!(),
+        }
+    }
+    Ok((crate_path, export_xml))
+}
@@ -0,0 +1,249 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Data, DeriveInput, Error as SynError, Fields, Member, Meta, PathArguments, Result as SynResult,
+    Type,
+};
+
+/// A field, which handles the interfaces of its type.
+/// The type of the field has to implement `dbus_async::Handler` with this crate.
+pub(crate) struct Delegate {
+    member: Member,
+    field_type: Type,
+}
+
+impl Delegate {
+    /// The code, which checks if the field handles the interface `interface`.
+    fn create_has_interface_code(&self) -> TokenStream {
+        let field_type = &self.field_type;
+        quote! {
+            <#field_type>::__dbus_has_interface(interface)
+        }
+    }
+
+    /// The code, which fails to compile, if the field handles the interface `interface`, which is
+    /// also declared by the type itself. Otherwise, it would be ambiguous, which one handles the
+    /// calls.
+    fn create_not_has_interface_code(&self, interface: &str) -> TokenStream {
+        let field_type = &self.field_type;
+        let message = format!(
+            "the interface {} is declared by the type and by the delegate field {}",
+            interface,
+            member_to_string(&self.member),
+        );
+        quote_spanned! {field_type.span()=>
+            const _: () = ::std::assert!(!<#field_type>::__dbus_has_interface(#interface), #message);
+        }
+    }
+
+    /// The code, which checks if the field has properties.
+    pub(super) fn create_has_properties_code(&self) -> TokenStream {
+        let field_type = &self.field_type;
+        quote! {
+            <#field_type>::__dbus_has_properties()
+        }
+    }
+
+    /// The code, which appends the names of the interfaces of the field to `names`.
+    pub(super) fn create_interface_names_code(&self) -> TokenStream {
+        let member = &self.member;
+        quote! {
+            self.#member.__dbus_get_interface_names(names);
+        }
+    }
+
+    /// The code, which appends the interfaces of the field with their properties to `interfaces`.
+    pub(super) fn create_interfaces_and_properties_code(&self) -> TokenStream {
+        let member = &self.member;
+        quote! {
+            self.#member.__dbus_get_interfaces_and_properties(dbus, header, interfaces).await?;
+        }
+    }
+
+    /// The code, which appends the introspection XML of the interfaces of the field to `xml`.
+    pub(super) fn create_introspect_code(&self, xml: &TokenStream) -> TokenStream {
+        let field_type = &self.field_type;
+        quote! {
+            <#field_type>::__dbus_introspect_interfaces(#xml);
+        }
+    }
+}
+
+fn member_to_string(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Check if the type is a plain path without generic arguments (e.g. `LifecycleImpl`).
+/// The type parameters of the struct are rejected, too.
+fn check_delegate_type(ast: &DeriveInput, field_type: &Type) -> SynResult<()> {
+    let is_plain_path = match field_type {
+        Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path
+                    .path
+                    .segments
+                    .iter()
+                    .all(|segment| matches!(segment.arguments, PathArguments::None))
+                && !ast
+                    .generics
+                    .type_params()
+                    .any(|type_param| type_path.path.is_ident(&type_param.ident))
+        }
+        _ => false,
+    };
+    if is_plain_path {
+        Ok(())
+    } else {
+        Err(SynError::new(
+            field_type.span(),
+            "the type of a delegate field cannot be generic, use a type, which derives Handler \
+            without generics",
+        ))
+    }
+}
+
+/// Get all fields, which have the `#[delegate]` attribute.
+pub(super) fn parse_delegates(ast: &DeriveInput) -> SynResult<Vec<Delegate>> {
+    let mut delegates = Vec::new();
+    let fields = match &ast.data {
+        Data::Struct(data_struct) => &data_struct.fields,
+        _ => return Ok(delegates),
+    };
+
+    let fields: Vec<_> = match fields {
+        Fields::Named(fields_named) => fields_named.named.iter().collect(),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    for (i, field) in fields.into_iter().enumerate() {
+        for attribute in &field.attrs {
+            if !attribute.path.is_ident("delegate") {
+                continue;
+            }
+            if !matches!(attribute.parse_meta()?, Meta::Path(_)) {
+                return Err(SynError::new(attribute.span(), "excepted \"delegate\""));
+            }
+
+            check_delegate_type(ast, &field.ty)?;
+
+            let member = if let Some(ident) = &field.ident {
+                Member::Named(ident.clone())
+            } else {
+                Member::Unnamed(i.into())
+            };
+            delegates.push(Delegate {
+                member,
+                field_type: field.ty.clone(),
+            });
+        }
+    }
+    Ok(delegates)
+}
+
+/// Create the code, which forwards the message to the field, if the field handles the interface.
+/// For the `org.freedesktop.DBus.Properties` interface the interface is the first argument.
+pub(super) fn create_delegates_code(delegates: &[Delegate]) -> Option<TokenStream> {
+    if delegates.is_empty() {
+        return None;
+    }
+
+    let mut forward_code = Vec::new();
+    for delegate in delegates {
+        let member = &delegate.member;
+        let has_interface = delegate.create_has_interface_code();
+        forward_code.push(quote! {
+            if #has_interface {
+                let msg = __dbus_message_parser::Message::new(header, body);
+                return __dbus_async::Handler::handle(&mut self.#member, dbus, msg).await;
+            }
+        });
+    }
+
+    let code = quote! {
+        let interface = match header.get_interface() {
+            ::std::option::Option::Some(interface) if interface == "org.freedesktop.DBus.Properties" => {
+                match body.first() {
+                    ::std::option::Option::Some(__dbus_message_parser::Value::String(interface)) => ::std::option::Option::Some(interface.clone()),
+                    _ => ::std::option::Option::None,
+                }
+            }
+            ::std::option::Option::Some(interface) => ::std::option::Option::Some(interface.to_string()),
+            ::std::option::Option::None => ::std::option::Option::None,
+        };
+        if let ::std::option::Option::Some(interface) = interface {
+            let interface: &str = interface.as_ref();
+            #(#forward_code)*
+        }
+    };
+    Some(code)
+}
+
+/// Create the hidden functions, which are used by the types, which delegate to this type.
+pub(super) fn create_delegate_functions_code(
+    interface_names: &[String],
+    have_properties: bool,
+    interfaces_xml: &str,
+    delegates: &[Delegate],
+) -> TokenStream {
+    let mut has_interface = Vec::new();
+    let mut not_has_interface = Vec::new();
+    let mut has_properties = Vec::new();
+    let mut introspect = Vec::new();
+    for delegate in delegates {
+        has_interface.push(delegate.create_has_interface_code());
+        for interface_name in interface_names {
+            not_has_interface.push(delegate.create_not_has_interface_code(interface_name));
+        }
+        has_properties.push(delegate.create_has_properties_code());
+        introspect.push(delegate.create_introspect_code(&quote! { xml }));
+    }
+
+    // The function is const, so the interfaces of the delegates can be checked at compile time
+    quote! {
+        #[doc(hidden)]
+        pub const fn __dbus_has_interface(interface: &str) -> bool {
+            const fn str_eq(a: &str, b: &str) -> bool {
+                let a = a.as_bytes();
+                let b = b.as_bytes();
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut i = 0;
+                while i < a.len() {
+                    if a[i] != b[i] {
+                        return false;
+                    }
+                    i += 1;
+                }
+                true
+            }
+
+            #(#not_has_interface)*
+
+            let names: &[&str] = &[#(#interface_names),*];
+            let mut i = 0;
+            while i < names.len() {
+                if str_eq(names[i], interface) {
+                    return true;
+                }
+                i += 1;
+            }
+            false #(|| #has_interface)*
+        }
+
+        #[doc(hidden)]
+        pub fn __dbus_has_properties() -> bool {
+            #have_properties #(|| #has_properties)*
+        }
+
+        #[doc(hidden)]
+        pub fn __dbus_introspect_interfaces(xml: &mut ::std::string::String) {
+            *xml += #interfaces_xml;
+            #(#introspect)*
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::helper::get_lit_str_from_lit;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Error as SynError, Lit, LitStr, Meta, MetaList, NestedMeta, Result as SynResult,
+};
+
+/// Split the `doc = "..."` entry from the other entries of a `MetaList`.
+/// The returned `MetaList` contains all other entries in the same order.
+pub(super) fn split_doc(meta_list: &MetaList) -> SynResult<(MetaList, Option<LitStr>)> {
+    let mut nested = Punctuated::new();
+    let mut doc = None;
+    for nested_meta in &meta_list.nested {
+        match nested_meta {
+            NestedMeta::Meta(Meta::NameValue(meta_name_value))
+                if meta_name_value.path.is_ident("doc") =>
+            {
+                if doc.is_some() {
+                    return Err(SynError::new(
+                        meta_name_value.path.get_ident().unwrap().span(),
+                        "doc is defined multiple times",
+                    ));
+                }
+                doc = Some(get_lit_str_from_lit(&meta_name_value.lit)?);
+            }
+            nested_meta => nested.push(nested_meta.clone()),
+        }
+    }
+
+    let meta_list = MetaList {
+        path: meta_list.path.clone(),
+        paren_token: meta_list.paren_token,
+        nested,
+    };
+    Ok((meta_list, doc))
+}
+
+/// Get the documentation of the `///` comments (`#[doc = "..."]` attributes).
+/// It returns `None` if there are no comments.
+pub(super) fn get_doc_from_attributes(attrs: &[Attribute]) -> Option<LitStr> {
+    let mut lines = Vec::new();
+    let mut span = None;
+    for attribute in attrs {
+        if !attribute.path.is_ident("doc") {
+            continue;
+        }
+        if let Ok(Meta::NameValue(meta_name_value)) = attribute.parse_meta() {
+            if let Lit::Str(lit_str) = meta_name_value.lit {
+                let line = lit_str.value();
+                // The space after `///` is not part of the documentation
+                lines.push(line.strip_prefix(' ').unwrap_or(&line).to_string());
+                span.get_or_insert(lit_str.span());
+            }
+        }
+    }
+    let doc = lines.join("\n");
+    let doc = doc.trim();
+    if doc.is_empty() {
+        None
+    } else {
+        Some(LitStr::new(doc, span?))
+    }
+}
+
+/// Write the documentation as an XML comment in front of the element with the given indentation.
+/// A documentation with multiple lines is written as a block.
+pub(super) fn doc_to_introspect(doc: Option<&LitStr>, xml: &mut String, indent: &str) {
+    let mut doc = match doc {
+        Some(doc) if !doc.value().trim().is_empty() => doc.value(),
+        _ => return,
+    };
+    // `--` is not allowed in a XML comment
+    while doc.contains("--") {
+        doc = doc.replace("--", "- -");
+    }
+    let lines: Vec<&str> = doc.lines().map(str::trim_end).collect();
+    if lines.len() == 1 {
+        *xml += &format!("{}<!-- {} -->\n", indent, lines[0]);
+    } else {
+        *xml += &format!("{}<!--\n", indent);
+        for line in lines {
+            if line.is_empty() {
+                *xml += "\n";
+            } else {
+                *xml += &format!("{}  {}\n", indent, line);
+            }
+        }
+        *xml += &format!("{}-->\n", indent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::doc_to_introspect;
+    use crate::xml::parse_xml;
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    fn introspect(doc: &str) -> String {
+        let doc = LitStr::new(doc, Span::call_site());
+        let mut xml = String::new();
+        doc_to_introspect(Some(&doc), &mut xml, "  ");
+        xml
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(introspect("Get the name."), "  <!-- Get the name. -->\n");
+        assert_eq!(introspect("   "), "");
+        let mut xml = String::new();
+        doc_to_introspect(None, &mut xml, "  ");
+        assert_eq!(xml, "");
+    }
+
+    #[test]
+    fn multiple_lines() {
+        assert_eq!(
+            introspect("First line.\n\nThird line.  "),
+            "  <!--\n    First line.\n\n    Third line.\n  -->\n"
+        );
+    }
+
+    #[test]
+    fn double_hyphens() {
+        assert_eq!(introspect("a -- b"), "  <!-- a - - b -->\n");
+        assert_eq!(introspect("a---b"), "  <!-- a- - -b -->\n");
+        assert_eq!(introspect("----"), "  <!-- - - - - -->\n");
+    }
+
+    #[test]
+    fn valid_xml_comments() {
+        for doc in &["a -- b", "----", "ends with -", "-\n--\n---"] {
+            let xml = format!(
+                "<node>\n{}  <interface name=\"a.b\"/>\n</node>",
+                introspect(doc)
+            );
+            let node = parse_xml(&xml).unwrap();
+            assert!(node.get_children()[0].get_doc().is_some(), "{}", doc);
+        }
+    }
+}
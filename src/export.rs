@@ -0,0 +1,104 @@
+use crate::helper::get_lit_str_from_lit;
+use crate::interface::Interface;
+use crate::introspectable::{Introspectable, START_XML};
+use std::env::var_os;
+use std::fs::{create_dir_all, write};
+use std::path::PathBuf;
+use syn::{Error as SynError, Lit, LitStr, Result as SynResult};
+
+/// The environment variable, which contains the directory of the exported XML files.
+static XML_DIR_VARIABLE: &str = "DBUS_ASYNC_DERIVE_XML_DIR";
+
+/// Which interface XML files are written during the build.
+pub(super) enum ExportXml {
+    /// Every interface is written into the file `<interface name>.xml`.
+    Interfaces(Lit),
+    /// The only interface is written into the given file.
+    File(LitStr),
+}
+
+impl ExportXml {
+    /// Parse `export_xml = true` or `export_xml = "file.xml"`.
+    /// It returns `None` for `export_xml = false`.
+    pub(super) fn try_from_lit(lit: &Lit) -> SynResult<Option<ExportXml>> {
+        match lit {
+            Lit::Bool(lit_bool) => {
+                if lit_bool.value {
+                    Ok(Some(ExportXml::Interfaces(lit.clone())))
+                } else {
+                    Ok(None)
+                }
+            }
+            lit => Ok(Some(ExportXml::File(get_lit_str_from_lit(lit)?))),
+        }
+    }
+
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            ExportXml::Interfaces(lit) => lit.span(),
+            ExportXml::File(file) => file.span(),
+        }
+    }
+}
+
+/// Get the directory of the exported XML files. The directory is given by the environment variable
+/// `DBUS_ASYNC_DERIVE_XML_DIR`. If it is not set then `OUT_DIR` is used, which is set if the crate
+/// has a build script.
+fn get_xml_dir() -> Option<PathBuf> {
+    var_os(XML_DIR_VARIABLE)
+        .or_else(|| var_os("OUT_DIR"))
+        .map(PathBuf::from)
+}
+
+/// Write a single interface as a XML file, which can be installed under
+/// `/usr/share/dbus-1/interfaces`.
+fn write_interface(export_xml: &ExportXml, path: PathBuf, interface: &Interface) -> SynResult<()> {
+    let mut xml = START_XML.trim_start().to_string();
+    xml.push('\n');
+    interface.to_introspect(&mut xml);
+    xml += "</node>\n";
+    write(&path, xml).map_err(|e| {
+        SynError::new(
+            export_xml.span(),
+            format!("could not write {}: {}", path.display(), e),
+        )
+    })
+}
+
+/// Write the XML files of the interfaces, if a directory is configured. Otherwise nothing is
+/// written.
+pub(super) fn export_interfaces(export_xml: &ExportXml, interfaces: &[Interface]) -> SynResult<()> {
+    if let ExportXml::File(file) = export_xml {
+        if interfaces.len() != 1 {
+            return Err(SynError::new(
+                file.span(),
+                "a file name can only be used for exactly one interface, use export_xml = true",
+            ));
+        }
+    }
+
+    let dir = if let Some(dir) = get_xml_dir() {
+        dir
+    } else {
+        return Ok(());
+    };
+    create_dir_all(&dir).map_err(|e| {
+        SynError::new(
+            export_xml.span(),
+            format!("could not create {}: {}", dir.display(), e),
+        )
+    })?;
+
+    match export_xml {
+        ExportXml::Interfaces(_) => {
+            for interface in interfaces {
+                let path = dir.join(format!("{}.xml", interface.get_name().value()));
+                write_interface(export_xml, path, interface)?;
+            }
+            Ok(())
+        }
+        ExportXml::File(file) => {
+            write_interface(export_xml, dir.join(file.value()), &interfaces[0])
+        }
+    }
+}
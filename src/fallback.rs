@@ -0,0 +1,43 @@
+use crate::helper::{get_ident_from_lit, get_ident_from_option_nested_meta, get_ident_from_path};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Error as SynError, Meta, Result as SynResult};
+
+/// Create the code, which forwards the message to the fallback function.
+/// The fallback function gets the header and all values of the body.
+pub(super) fn create_fallback_code(function: &Ident) -> TokenStream {
+    quote! {
+        {
+            let body: ::std::vec::Vec<__dbus_message_parser::Value> = body_iter.collect();
+            return self.#function(&dbus, &header, body).await;
+        }
+    }
+}
+
+/// Parse `fallback = function` or `fallback(function)`. As a struct attribute, the function has to be
+/// given as a string (`#[fallback = "function"]`), because the compiler only allows literals there.
+pub(super) fn parse_fallback(meta: &Meta) -> SynResult<Ident> {
+    let meta_type = get_ident_from_path(meta.path())?;
+    if meta_type != "fallback" {
+        return Err(SynError::new(meta_type.span(), "excepted \"fallback\""));
+    }
+    match meta {
+        Meta::NameValue(meta_name_value) => get_ident_from_lit(&meta_name_value.lit),
+        Meta::List(meta_list) => {
+            let nested_iter = &mut meta_list.nested.iter();
+
+            // Get the name of the function to call
+            let function = get_ident_from_option_nested_meta(nested_iter.next())?;
+
+            if nested_iter.next().is_some() {
+                return Err(SynError::new(meta_list.span(), "too many arguments"));
+            }
+            Ok(function)
+        }
+        Meta::Path(path) => Err(SynError::new(
+            path.span(),
+            "excepted \"fallback = function\"",
+        )),
+    }
+}
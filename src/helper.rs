@@ -1,34 +1,128 @@
-use crate::signature::SignatureIterator;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Group, Ident, Literal, Span, TokenStream, TokenTree};
+use quote::quote;
 use syn::spanned::Spanned;
 use syn::{
-    Error as SynError, Lit, LitBool, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Path,
-    Result as SynResult,
+    Attribute, Data, Error as SynError, Field, Fields, Lit, LitBool, LitStr, Meta, MetaList,
+    MetaNameValue, NestedMeta, Path, Result as SynResult,
 };
 
-pub(super) fn get_signatures_from_nested_meta(
-    nested_meta: &NestedMeta,
-    vec_signature: &mut Vec<(String, TokenStream, TokenStream, TokenStream)>,
-) -> SynResult<()> {
-    // Get the signature of the parameters
-    let signature = get_lit_str_from_nested_meta(nested_meta)?;
-    let signature_iter = SignatureIterator::from(&signature);
-    for signature in signature_iter {
-        vec_signature.push(signature?);
+/// Replace the identifiers in `key = ident` by string literals, so the tokens can be parsed as a
+/// `Meta`. The string literal keeps the span of the identifier.
+fn quote_ident_values(tokens: TokenStream) -> TokenStream {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut result = Vec::with_capacity(tokens.len());
+    for (i, token) in tokens.iter().enumerate() {
+        let token = match token {
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), quote_ident_values(group.stream()));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            TokenTree::Ident(ident) if ident != "true" && ident != "false" => {
+                let after_equal = i >= 2
+                    && matches!(&tokens[i - 1], TokenTree::Punct(punct) if punct.as_char() == '=')
+                    && matches!(&tokens[i - 2], TokenTree::Ident(_));
+                let is_last = match tokens.get(i + 1) {
+                    Some(TokenTree::Punct(punct)) => punct.as_char() == ',',
+                    Some(_) => false,
+                    None => true,
+                };
+                if after_equal && is_last {
+                    let mut literal = Literal::string(&ident.to_string());
+                    literal.set_span(ident.span());
+                    TokenTree::Literal(literal)
+                } else {
+                    token.clone()
+                }
+            }
+            token => token.clone(),
+        };
+        result.push(token);
     }
-    Ok(())
+    result.into_iter().collect()
 }
 
-pub(super) fn get_signatures_from_option_nested_meta(
-    option: Option<&NestedMeta>,
-    vec_signature: &mut Vec<(String, TokenStream, TokenStream, TokenStream)>,
-) -> SynResult<()> {
-    match option {
-        Some(nested_meta) => get_signatures_from_nested_meta(nested_meta, vec_signature),
-        None => Err(SynError::new(Span::call_site(), "excepted nested_meta")),
+/// Parse the attribute as a `Meta`. In contrast to `Attribute::parse_meta`, an identifier is
+/// allowed as the value of `key = value`. It is converted to a string literal.
+pub(super) fn parse_meta(attribute: &Attribute) -> SynResult<Meta> {
+    let path = &attribute.path;
+    let tokens = quote_ident_values(attribute.tokens.clone());
+    syn::parse2(quote! { #path #tokens })
+}
+
+/// Get all fields of a struct. For an enum or a union, there are no fields.
+pub(super) fn get_struct_fields(data: &Data) -> Vec<&Field> {
+    match data {
+        Data::Struct(data_struct) => match &data_struct.fields {
+            Fields::Named(fields_named) => fields_named.named.iter().collect(),
+            Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => Vec::new(),
     }
 }
 
+/// Check if the arguments are given by the named-key syntax (`key = value, ...`).
+pub(super) fn is_named_syntax(meta_list: &MetaList) -> bool {
+    matches!(
+        meta_list.nested.first(),
+        Some(NestedMeta::Meta(Meta::NameValue(_)))
+    )
+}
+
+/// Get all `key = value` arguments of the named-key syntax.
+/// Every key has to be one of `keys` and can only be defined once.
+pub(super) fn get_named_arguments<'a>(
+    meta_list: &'a MetaList,
+    keys: &[&str],
+) -> SynResult<Vec<(Ident, &'a Lit)>> {
+    let mut arguments: Vec<(Ident, &Lit)> = Vec::new();
+    for nested_meta in &meta_list.nested {
+        let meta_name_value = get_meta_name_value_from_nested_meta(nested_meta)?;
+        let key = get_ident_from_path(&meta_name_value.path)?;
+        let key_string = key.to_string();
+        if !keys.contains(&key_string.as_str()) {
+            return Err(SynError::new(
+                key.span(),
+                format!(
+                    "Unknown key: {}{}",
+                    key_string,
+                    did_you_mean(&key_string, keys)
+                ),
+            ));
+        }
+        if arguments.iter().any(|(other, _)| *other == key) {
+            return Err(SynError::new(
+                key.span(),
+                format!("{} is defined twice", key_string),
+            ));
+        }
+        arguments.push((key, &meta_name_value.lit));
+    }
+    Ok(arguments)
+}
+
+/// Get the value of a required key of the named-key syntax.
+pub(super) fn get_required_argument<T>(
+    meta_list: &MetaList,
+    key: &str,
+    value: Option<T>,
+) -> SynResult<T> {
+    match value {
+        Some(value) => Ok(value),
+        None => Err(SynError::new(
+            meta_list.span(),
+            format!("no \"{}\" defined", key),
+        )),
+    }
+}
+
+/// Get the identifier of a function, which is given as `key = function` or `key = "function"`.
+pub(super) fn get_ident_from_lit(lit: &Lit) -> SynResult<Ident> {
+    get_lit_str_from_lit(lit)?.parse()
+}
+
 pub(super) fn get_meta_list_from_meta(meta: &Meta) -> SynResult<&MetaList> {
     match meta {
         Meta::List(meta_list) => Ok(meta_list),
@@ -91,7 +185,7 @@ pub(super) fn get_lit_str_from_option_nested_meta(param: Option<&NestedMeta>) ->
     }
 }
 
-fn get_lit_bool_from_lit(lit: &Lit) -> SynResult<LitBool> {
+pub(super) fn get_lit_bool_from_lit(lit: &Lit) -> SynResult<LitBool> {
     match lit {
         Lit::Bool(lit_bool) => Ok(lit_bool.clone()),
         lit => Err(SynError::new(lit.span(), "excepted bool")),
@@ -139,3 +233,130 @@ pub(super) fn escape_signature(signature: &str) -> String {
     let signature = signature.replace('{', "{{");
     signature.replace('}', "}}")
 }
+
+/// Compute the edit distance between two strings, where swapping two adjacent characters counts
+/// as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// Create a suggestion with the closest keyword, if there is a keyword, which is close enough.
+pub(super) fn did_you_mean(value: &str, keywords: &[&str]) -> String {
+    let max_distance = (value.chars().count() / 3).max(1);
+    let closest = keywords
+        .iter()
+        .map(|keyword| (edit_distance(value, keyword), keyword))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance);
+    if let Some((_, keyword)) = closest {
+        format!(", did you mean `{}`?", keyword)
+    } else {
+        String::new()
+    }
+}
+
+/// Convert a camel case DBus name into a snake case function name (`NameChanged` -> `name_changed`).
+/// A sequence of capital letters is treated as one word (`HTTPDone` -> `http_done`).
+pub(super) fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lowercase)
+            {
+                result.push('_');
+            }
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+/// Get the default name of the function, which emits a signal of the member `member` of the
+/// interface `interface`. The name is qualified by the interface, so the functions of different
+/// interfaces do not collide (`org.example.interface` and `NameChanged` ->
+/// `emit_org_example_interface_name_changed`).
+pub(super) fn get_default_emit_function_name(interface: &LitStr, member: &LitStr) -> String {
+    format!(
+        "emit_{}_{}",
+        to_snake_case(&interface.value().replace('.', "_")),
+        to_snake_case(&member.value())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{did_you_mean, edit_distance, get_default_emit_function_name, to_snake_case};
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("property", "property"), 0);
+        assert_eq!(edit_distance("propery", "property"), 1);
+        assert_eq!(edit_distance("gte", "get"), 1);
+        assert_eq!(edit_distance("ab", "ba"), 1);
+        assert_eq!(edit_distance("", "set"), 3);
+        assert_eq!(edit_distance("method", "signal"), 6);
+    }
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(
+            did_you_mean("propery", &["method", "property", "signal"]),
+            ", did you mean `property`?"
+        );
+        assert_eq!(
+            did_you_mean("gte", &["get", "set"]),
+            ", did you mean `get`?"
+        );
+        assert_eq!(did_you_mean("xyz", &["get", "set"]), "");
+        assert_eq!(did_you_mean("emit", &["method", "signal"]), "");
+    }
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("NameChanged"), "name_changed");
+        assert_eq!(to_snake_case("HTTPDone"), "http_done");
+        assert_eq!(to_snake_case("GetHTTPResponse"), "get_http_response");
+        assert_eq!(to_snake_case("Version2Ready"), "version2_ready");
+        assert_eq!(to_snake_case("ready"), "ready");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+        assert_eq!(to_snake_case(""), "");
+    }
+
+    #[test]
+    fn default_emit_function_name() {
+        let interface = LitStr::new("org.example.Interface", Span::call_site());
+        let member = LitStr::new("NameChanged", Span::call_site());
+        assert_eq!(
+            get_default_emit_function_name(&interface, &member),
+            "emit_org_example_interface_name_changed"
+        );
+    }
+}
@@ -1,33 +1,218 @@
+use crate::annotation::{annotations_to_introspect, Annotation};
 use crate::code::{
     get_member_from_header, unknown_member_from_header, unknown_property_from_header,
 };
+use crate::doc::doc_to_introspect;
+use crate::fallback::{create_fallback_code, parse_fallback};
 use crate::helper::{
-    get_ident_from_path, get_lit_str_from_option_nested_meta, get_meta_list_from_nested_meta,
+    did_you_mean, get_ident_from_path, get_lit_str_from_lit, get_lit_str_from_option_nested_meta,
+    get_meta_list_from_nested_meta,
 };
 use crate::introspectable::Introspectable;
 use crate::method::Method;
+use crate::name::{check_duplicates, check_interface_name, check_standard_interface};
+use crate::properties::GetAllPolicy;
 use crate::property::Property;
 use crate::signal::Signal;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use std::convert::TryFrom;
-use syn::{Error as SynError, LitStr, MetaList};
+use syn::spanned::Spanned;
+use syn::{Error as SynError, Field, LitStr, Meta, MetaList, NestedMeta, Result as SynResult};
+
+/// The attributes of an interface, which are suggested if an unknown attribute is given.
+const INTERFACE_ATTRIBUTES: [&str; 9] = [
+    "method",
+    "property",
+    "signal",
+    "annotation",
+    "fallback",
+    "replace",
+    "emit_on_set",
+    "get_all",
+    "doc",
+];
 
 pub(crate) struct Interface {
     name: LitStr,
     methods: Vec<Method>,
     properties: Vec<Property>,
     signals: Vec<Signal>,
+    fallback: Option<Ident>,
+    replace: bool,
+    emit_on_set: bool,
+    get_all: GetAllPolicy,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
 }
 
 impl Interface {
+    pub(super) fn new(
+        name: LitStr,
+        methods: Vec<Method>,
+        properties: Vec<Property>,
+        signals: Vec<Signal>,
+        annotations: Vec<Annotation>,
+        doc: Option<LitStr>,
+    ) -> SynResult<Interface> {
+        check_interface_name(&name)?;
+        let interface = Interface {
+            name,
+            methods,
+            properties,
+            signals,
+            fallback: None,
+            replace: false,
+            emit_on_set: false,
+            get_all: GetAllPolicy::Fail,
+            annotations,
+            doc,
+        };
+        interface.check_duplicates()?;
+        Ok(interface)
+    }
+
+    /// Check if a method, a property, a signal or an annotation is defined multiple times.
+    fn check_duplicates(&self) -> SynResult<()> {
+        check_duplicates(self.methods.iter().map(Method::get_name), "method")?;
+        check_duplicates(self.properties.iter().map(Property::get_name), "property")?;
+        check_duplicates(self.signals.iter().map(Signal::get_name), "signal")?;
+        check_duplicates(
+            self.annotations.iter().map(Annotation::get_name),
+            "annotation",
+        )
+    }
+
+    pub(super) fn get_name(&self) -> &LitStr {
+        &self.name
+    }
+
+    /// Infer the signatures of the field-backed properties from the types of the fields.
+    pub(super) fn infer_signatures(&mut self, fields: &[&Field]) -> SynResult<()> {
+        for property in &mut self.properties {
+            property.infer_signature(fields)?;
+        }
+        Ok(())
+    }
+
+    /// Check if this interface collides with one of the generated standard interfaces
+    /// (`generated`).
+    pub(super) fn check_standard_interface(&self, generated: &[&str]) -> SynResult<()> {
+        check_standard_interface(&self.name, self.replace, generated)
+    }
+
+    pub(super) fn has_properties(&self) -> bool {
+        !self.properties.is_empty()
+    }
+
+    /// Check if this interface replaces the generated implementation of the standard interface
+    /// `name`.
+    pub(super) fn is_replacing(&self, name: &str) -> bool {
+        self.replace && self.name.value() == name
+    }
+
+    /// Get the names of the functions, which emit the signals of this interface, including the
+    /// `PropertiesChanged` signal of the properties. Only the names, which are chosen by
+    /// `emit_fn`, are returned, because the default names are qualified by the interface and
+    /// cannot collide.
+    pub(super) fn get_emit_function_names(&self) -> Vec<LitStr> {
+        let mut names: Vec<LitStr> = self
+            .signals
+            .iter()
+            .filter_map(Signal::get_emit_function)
+            .map(|function| LitStr::new(&function.to_string(), function.span()))
+            .collect();
+        names.extend(
+            self.properties
+                .iter()
+                .filter_map(Property::get_emit_function)
+                .map(|function| LitStr::new(&function.to_string(), function.span())),
+        );
+        names
+    }
+
+    /// Create the functions, which emit the signals of this interface, including the
+    /// `PropertiesChanged` signal of the properties.
+    pub(super) fn create_emit_functions_code(&self) -> Vec<TokenStream> {
+        let mut functions: Vec<TokenStream> = self
+            .signals
+            .iter()
+            .map(|signal| signal.create_emit_function_code(&self.name))
+            .collect();
+        functions.extend(
+            self.properties
+                .iter()
+                .filter_map(|property| property.create_emit_function_code(&self.name)),
+        );
+        functions
+    }
+
+    /// Get the name of the constant, which contains the introspection XML of this interface (e.g.
+    /// `INTROSPECTION_XML_ORG_EXAMPLE_INTERFACE` for `org.example.interface`).
+    pub(super) fn get_introspection_const_name(&self) -> LitStr {
+        let name = format!(
+            "INTROSPECTION_XML_{}",
+            self.name.value().replace('.', "_").to_uppercase()
+        );
+        LitStr::new(&name, self.name.span())
+    }
+
+    /// Create the constant, which contains the introspection XML of this interface.
+    pub(super) fn create_introspection_const_code(&self) -> TokenStream {
+        let name = Ident::new(
+            &self.get_introspection_const_name().value(),
+            self.name.span(),
+        );
+        let doc = format!(
+            "The introspection XML of the interface `{}`.",
+            self.name.value()
+        );
+        let mut xml = String::new();
+        self.to_introspect(&mut xml);
+        quote! {
+            #[doc = #doc]
+            pub const #name: &'static str = #xml;
+        }
+    }
+
+    /// Create the declarations of the handler functions of the methods and the properties in the
+    /// trait of this interface.
+    pub(super) fn create_trait_functions_code(&self) -> Vec<TokenStream> {
+        let mut functions: Vec<TokenStream> = self
+            .methods
+            .iter()
+            .map(|method| method.create_trait_function_code(&self.name))
+            .collect();
+        for property in &self.properties {
+            functions.extend(property.create_trait_functions_code(&self.name));
+        }
+        functions
+    }
+
+    /// Create the checks of the types of all functions and fields of this interface.
+    /// It returns the names of the check functions and the items of the checks.
+    pub(super) fn create_check_code(&self) -> Vec<(Ident, TokenStream)> {
+        let mut checks = Vec::new();
+        for method in &self.methods {
+            checks.extend(method.create_check_code());
+        }
+        for property in &self.properties {
+            checks.extend(property.create_check_code());
+        }
+        checks
+    }
+
     pub(super) fn create_methods_code(&self) -> Option<TokenStream> {
-        if self.methods.is_empty() {
+        if self.methods.is_empty() && self.fallback.is_none() {
             return None;
         }
 
         let get_member_from_header = get_member_from_header();
-        let unknown_member_from_header = unknown_member_from_header();
+        let unknown_member_from_header = if let Some(fallback) = &self.fallback {
+            create_fallback_code(fallback)
+        } else {
+            unknown_member_from_header()
+        };
         let name = &self.name;
         let mut methods = Vec::new();
         for method in &self.methods {
@@ -44,79 +229,114 @@ impl Interface {
         Some(code)
     }
 
-    pub(super) fn create_set_code(&self) -> Option<TokenStream> {
+    /// Create the code, which sets the property `property` of this interface. If the interface
+    /// has no properties, then a `org.freedesktop.DBus.Error.UnknownProperty` error is returned.
+    pub(super) fn create_set_code(&self) -> TokenStream {
+        let unknown_property_from_header = unknown_property_from_header();
+        let name = &self.name;
         if self.properties.is_empty() {
-            return None;
+            return quote! {
+                #name => #unknown_property_from_header
+            };
         }
 
-        let unknown_property_from_header = unknown_property_from_header();
-        let name = &self.name;
         let mut properties = Vec::new();
         for property in &self.properties {
-            properties.push(property.create_set_code());
+            properties.push(property.create_set_code(name, self.emit_on_set));
         }
-        let code = quote! {
+        quote! {
             #name => {
                 match property.as_ref() {
                     #(#properties)*
                     property => #unknown_property_from_header
                 }
             }
-        };
-        Some(code)
+        }
     }
 
-    pub(super) fn create_get_code(&self) -> Option<TokenStream> {
+    /// Create the code, which gets the property `property` of this interface. If the interface
+    /// has no properties, then a `org.freedesktop.DBus.Error.UnknownProperty` error is returned.
+    pub(super) fn create_get_code(&self) -> TokenStream {
+        let unknown_property_from_header = unknown_property_from_header();
+        let name = &self.name;
         if self.properties.is_empty() {
-            return None;
+            return quote! {
+                #name => #unknown_property_from_header
+            };
         }
 
-        let unknown_property_from_header = unknown_property_from_header();
-        let name = &self.name;
         let mut properties = Vec::new();
         for property in &self.properties {
             properties.push(property.create_get_code());
         }
-        let code = quote! {
+        quote! {
             #name => {
                 match property.as_ref() {
                     #(#properties)*
                     property => #unknown_property_from_header
                 }
             }
-        };
-        Some(code)
+        }
     }
 
-    pub(super) fn create_get_all_code(&self) -> Option<TokenStream> {
-        if self.properties.is_empty() {
-            return None;
+    /// Create the code, which gets all readable properties as a dict (`a{sv}`).
+    /// The `GetAll` failure policy of the interface is applied.
+    pub(super) fn create_properties_dict_code(&self) -> TokenStream {
+        let mut properties = Vec::new();
+        for property in &self.properties {
+            if let Some(dict_entry) = property.create_dict_entry_code(&self.name, self.get_all) {
+                properties.push(dict_entry);
+            }
+        }
+        if properties.is_empty() {
+            quote! {
+                __dbus_message_parser::Value::Array(::std::vec::Vec::new(), "{sv}".to_string())
+            }
+        } else {
+            quote! {
+                {
+                    let mut o = ::std::vec::Vec::new();
+                    #(#properties)*
+                    __dbus_message_parser::Value::Array(o, "{sv}".to_string())
+                }
+            }
         }
+    }
 
+    /// Create the code, which gets all readable properties of this interface as dict entries
+    /// (`{sv}`). If the interface has no readable properties, then there are no entries.
+    pub(super) fn create_get_all_code(&self) -> TokenStream {
         let name = &self.name;
         let mut properties = Vec::new();
         for property in &self.properties {
-            if let Some(get_all_property) = property.create_get_all_code() {
-                properties.push(quote! {
-                    {
-                        o.push(#get_all_property);
-                    }
-                });
+            if let Some(get_all_property) = property.create_get_all_code(name, self.get_all) {
+                properties.push(get_all_property);
             }
         }
-        let code = quote! {
-            #name => {
-                let mut o = Vec::new();
-                #(#properties)*
-                o
+        if properties.is_empty() {
+            quote! {
+                #name => ::std::vec::Vec::new(),
             }
-        };
-        Some(code)
+        } else {
+            quote! {
+                #name => {
+                    let mut o = ::std::vec::Vec::new();
+                    #(#properties)*
+                    o
+                }
+            }
+        }
+    }
+
+    /// Check if the generated code of this interface uses the `log` crate.
+    pub(super) fn uses_log(&self) -> bool {
+        self.get_all == GetAllPolicy::Log && self.properties.iter().any(Property::is_readable)
     }
 }
 
 impl Introspectable for Interface {
     fn to_introspect(&self, xml: &mut String) {
+        doc_to_introspect(self.doc.as_ref(), xml, "  ");
         *xml += &format!("  <interface name=\"{}\">\n", self.name.value());
         for method in &self.methods {
             method.to_introspect(xml);
@@ -127,6 +347,7 @@ impl Introspectable for Interface {
         for signal in &self.signals {
             signal.to_introspect(xml);
         }
+        annotations_to_introspect(&self.annotations, xml, "    ");
         *xml += "  </interface>\n";
     }
 }
@@ -146,11 +367,84 @@ impl TryFrom<&MetaList> for Interface {
         let nested_iter = &mut meta_list.nested.iter();
         // Get the interface name
         let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        check_interface_name(&name)?;
 
         let mut methods = Vec::new();
         let mut properties = Vec::new();
         let mut signals = Vec::new();
+        let mut fallback = None;
+        let mut replace = false;
+        let mut emit_on_set = false;
+        let mut get_all = None;
+        let mut annotations = Vec::new();
+        let mut doc = None;
         for nested_meta in nested_iter {
+            if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested_meta {
+                if meta_name_value.path.is_ident("doc") {
+                    if doc.is_some() {
+                        return Err(SynError::new(
+                            meta_name_value.path.span(),
+                            "doc is defined multiple times",
+                        ));
+                    }
+                    doc = Some(get_lit_str_from_lit(&meta_name_value.lit)?);
+                    continue;
+                }
+                if meta_name_value.path.is_ident("fallback") {
+                    if fallback.is_some() {
+                        return Err(SynError::new(
+                            meta_name_value.path.span(),
+                            "Fallback is defined multiple times",
+                        ));
+                    }
+                    fallback = Some(parse_fallback(&Meta::NameValue(meta_name_value.clone()))?);
+                    continue;
+                }
+                if meta_name_value.path.is_ident("get_all") {
+                    if get_all.is_some() {
+                        return Err(SynError::new(
+                            meta_name_value.path.span(),
+                            "get_all is defined multiple times",
+                        ));
+                    }
+                    get_all = Some(GetAllPolicy::try_from(&meta_name_value.lit)?);
+                    continue;
+                }
+            }
+            if let NestedMeta::Meta(Meta::Path(path)) = nested_meta {
+                if path.is_ident("replace") {
+                    if replace {
+                        return Err(SynError::new(
+                            path.span(),
+                            "Replace is defined multiple times",
+                        ));
+                    }
+                    replace = true;
+                    continue;
+                }
+                if path.is_ident("emit_on_set") {
+                    if emit_on_set {
+                        return Err(SynError::new(
+                            path.span(),
+                            "emit_on_set is defined multiple times",
+                        ));
+                    }
+                    emit_on_set = true;
+                    continue;
+                }
+            }
+            if let NestedMeta::Meta(meta @ (Meta::Path(_) | Meta::NameValue(_))) = nested_meta {
+                let ident = get_ident_from_path(meta.path())?;
+                let attribute = ident.to_string();
+                return Err(SynError::new(
+                    ident.span(),
+                    format!(
+                        "Unknown attribute: {}{}",
+                        attribute,
+                        did_you_mean(&attribute, &INTERFACE_ATTRIBUTES)
+                    ),
+                ));
+            }
             let meta_list = get_meta_list_from_nested_meta(nested_meta)?;
             let ident = get_ident_from_path(&meta_list.path)?;
             match ident.to_string().as_ref() {
@@ -166,20 +460,47 @@ impl TryFrom<&MetaList> for Interface {
                     let signal = Signal::try_from(meta_list)?;
                     signals.push(signal);
                 }
+                "annotation" => {
+                    let annotation = Annotation::try_from(meta_list)?;
+                    annotations.push(annotation);
+                }
+                "fallback" => {
+                    if fallback.is_some() {
+                        return Err(SynError::new(
+                            ident.span(),
+                            "Fallback is defined multiple times",
+                        ));
+                    }
+                    fallback = Some(parse_fallback(&Meta::List(meta_list.clone()))?);
+                }
                 attribute => {
                     return Err(SynError::new(
                         ident.span(),
-                        format!("Unknown attribute: {}", attribute),
+                        format!(
+                            "Unknown attribute: {}{}",
+                            attribute,
+                            did_you_mean(attribute, &INTERFACE_ATTRIBUTES)
+                        ),
                     ))
                 }
             }
         }
 
-        Ok(Interface {
+        // The collisions with the generated standard interfaces are checked by the object
+        check_standard_interface(&name, replace, &[])?;
+        let interface = Interface {
             name,
             methods,
             properties,
             signals,
-        })
+            fallback,
+            replace,
+            emit_on_set,
+            get_all: get_all.unwrap_or(GetAllPolicy::Fail),
+            annotations,
+            doc,
+        };
+        interface.check_duplicates()?;
+        Ok(interface)
     }
 }
@@ -0,0 +1,528 @@
+use crate::annotation::{split_annotations, Annotation, DEPRECATED};
+use crate::argument::{get_arguments_from_lit_str, Argument};
+use crate::crate_path::parse_dbus;
+use crate::doc::{get_doc_from_attributes, split_doc};
+use crate::export::export_interfaces;
+use crate::helper::{
+    get_ident_from_path, get_lit_str_from_nested_meta, get_meta_list_from_meta, parse_meta,
+};
+use crate::interface::Interface;
+use crate::method::Method;
+use crate::name::check_member_name;
+use crate::object::Object;
+use crate::property::Property;
+use crate::signature::{signature_from_type, SignatureIterator};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::convert::TryFrom;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Error as SynError, FnArg, GenericArgument, ImplItem, ImplItemMethod, ItemImpl,
+    LitStr, Meta, Pat, PathArguments, Result as SynResult, ReturnType, Type,
+};
+
+/// The kind of a member, which is defined by an attribute on a function.
+enum MemberKind {
+    Method,
+    Get,
+    Set,
+}
+
+/// A member, which is defined by an attribute on a function.
+struct MemberAttribute {
+    kind: MemberKind,
+    span: Span,
+    name: Option<LitStr>,
+    signatures: Vec<LitStr>,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
+}
+
+/// Convert a snake case function name into a camel case DBus name (`get_value` -> `GetValue`).
+fn to_camel_case(function: &str) -> String {
+    let mut result = String::new();
+    for part in function.split('_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
+
+/// Get the DBus name of a member. If no name was given then it is derived from the function name.
+fn get_name(member: &MemberAttribute, function: &Ident, prefix: &str) -> LitStr {
+    if let Some(name) = &member.name {
+        name.clone()
+    } else {
+        let function_name = function.to_string();
+        let function_name = function_name.strip_prefix(prefix).unwrap_or(&function_name);
+        LitStr::new(&to_camel_case(function_name), function.span())
+    }
+}
+
+/// Parse a single signature.
+fn get_signatures(
+    signature: &str,
+    span: Span,
+) -> SynResult<Vec<(String, TokenStream, TokenStream, TokenStream)>> {
+    let signature = LitStr::new(signature, span);
+    SignatureIterator::from(&signature).collect()
+}
+
+/// Parse a member attribute: `#[method]`, `#[method("Name")]` or `#[method("Name", "in", "out")]`.
+/// The attribute can contain `annotation("name", "value")` entries and a `doc = "..."` entry.
+/// It returns `None` if the attribute does not define a member.
+fn parse_member_attribute(attribute: &Attribute) -> SynResult<Option<MemberAttribute>> {
+    let kind = if attribute.path.is_ident("method") {
+        MemberKind::Method
+    } else if attribute.path.is_ident("get") {
+        MemberKind::Get
+    } else if attribute.path.is_ident("set") {
+        MemberKind::Set
+    } else {
+        return Ok(None);
+    };
+
+    let mut name = None;
+    let mut signatures = Vec::new();
+    let mut annotations = Vec::new();
+    let mut doc = None;
+    match parse_meta(attribute)? {
+        Meta::Path(_) => {}
+        Meta::List(meta_list) => {
+            let (meta_list, member_annotations) = split_annotations(&meta_list)?;
+            annotations = member_annotations;
+            let (meta_list, member_doc) = split_doc(&meta_list)?;
+            doc = member_doc;
+            let nested_iter = &mut meta_list.nested.iter();
+            if let Some(nested_meta) = nested_iter.next() {
+                name = Some(get_lit_str_from_nested_meta(nested_meta)?);
+            }
+            for nested_meta in nested_iter {
+                signatures.push(get_lit_str_from_nested_meta(nested_meta)?);
+            }
+            let max_signatures = match kind {
+                MemberKind::Method => 2,
+                MemberKind::Get | MemberKind::Set => 1,
+            };
+            if signatures.len() > max_signatures {
+                return Err(SynError::new(meta_list.span(), "too many arguments"));
+            }
+        }
+        Meta::NameValue(name_value) => {
+            return Err(SynError::new(
+                name_value.span(),
+                "excepted a MetaList got NameValue",
+            ))
+        }
+    }
+
+    let span = get_ident_from_path(&attribute.path)?.span();
+    Ok(Some(MemberAttribute {
+        kind,
+        span,
+        name,
+        signatures,
+        annotations,
+        doc,
+    }))
+}
+
+/// Get the `org.freedesktop.DBus.Deprecated` annotation, if the function has a `#[deprecated]`
+/// attribute.
+fn get_deprecated_annotation(method: &ImplItemMethod) -> Option<Annotation> {
+    let attribute = method
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path.is_ident("deprecated"))?;
+    let span = attribute.path.span();
+    Some(Annotation::new(
+        LitStr::new(DEPRECATED, span),
+        LitStr::new("true", span),
+    ))
+}
+
+/// Add the annotations of a member to the annotations of a property. The getter and the setter
+/// can have the same annotation, but only with the same value.
+fn add_annotations(
+    annotations: &mut Vec<Annotation>,
+    member_annotations: Vec<Annotation>,
+) -> SynResult<()> {
+    for annotation in member_annotations {
+        let name = annotation.get_name();
+        if let Some(other) = annotations
+            .iter()
+            .find(|other| other.get_name().value() == name.value())
+        {
+            if other.get_value().value() != annotation.get_value().value() {
+                return Err(SynError::new(
+                    name.span(),
+                    format!(
+                        "annotation {:?} is defined with the value {:?} and {:?}",
+                        name.value(),
+                        other.get_value().value(),
+                        annotation.get_value().value()
+                    ),
+                ));
+            }
+        } else {
+            annotations.push(annotation);
+        }
+    }
+    Ok(())
+}
+
+/// Get the arguments, which are passed through the DBus, of a function.
+/// The first two arguments after `self` are always the `DBus` and the `MessageHeader`.
+/// The name is `None`, if the argument is not a simple identifier pattern.
+fn get_arguments(method: &ImplItemMethod) -> SynResult<Vec<(Option<Ident>, &Type)>> {
+    let sig = &method.sig;
+    if sig.asyncness.is_none() {
+        return Err(SynError::new(
+            sig.fn_token.span(),
+            "excepted an async function",
+        ));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(_)) => {}
+        _ => {
+            return Err(SynError::new(
+                sig.inputs.span(),
+                "excepted `&mut self` as the first argument",
+            ))
+        }
+    }
+
+    let mut arguments = Vec::new();
+    for (i, input) in inputs.enumerate() {
+        if let FnArg::Typed(pat_type) = input {
+            // Skip the `dbus` and the `msg_header` argument
+            if i < 2 {
+                continue;
+            }
+            let name = if let Pat::Ident(pat_ident) = pat_type.pat.as_ref() {
+                Some(pat_ident.ident.clone())
+            } else {
+                None
+            };
+            arguments.push((name, pat_type.ty.as_ref()));
+        }
+    }
+
+    if sig.inputs.len() < 3 {
+        return Err(SynError::new(
+            sig.inputs.span(),
+            "excepted the arguments `dbus: &DBus` and `msg_header: &MessageHeader`",
+        ));
+    }
+    Ok(arguments)
+}
+
+/// Get the type `T` of the return type `Result<T, (Error, String)>`.
+fn get_ok_type(method: &ImplItemMethod) -> SynResult<&Type> {
+    if let ReturnType::Type(_, return_type) = &method.sig.output {
+        if let Type::Path(type_path) = return_type.as_ref() {
+            if let Some(segment) = type_path.path.segments.last() {
+                if segment.ident == "Result" {
+                    if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                        if let Some(GenericArgument::Type(ok_type)) = arguments.args.first() {
+                            return Ok(ok_type);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Err(SynError::new(
+        method.sig.output.span(),
+        "excepted the return type `Result<T, (Error, String)>`",
+    ))
+}
+
+/// Get the output signatures from the `Ok` type.
+/// A tuple is split into multiple output arguments.
+fn get_output_signatures(ok_type: &Type) -> SynResult<String> {
+    match ok_type {
+        Type::Tuple(type_tuple) => {
+            let mut signature = String::new();
+            for elem in &type_tuple.elems {
+                signature += &signature_from_type(elem)?;
+            }
+            Ok(signature)
+        }
+        ok_type => signature_from_type(ok_type),
+    }
+}
+
+fn create_method(member: MemberAttribute, method: &ImplItemMethod) -> SynResult<Method> {
+    let function = method.sig.ident.clone();
+    let name = get_name(&member, &function, "");
+    check_member_name(&name, "method")?;
+    let arguments = get_arguments(method)?;
+
+    let (input_signatures, mut input_arguments) = if let Some(signature) = member.signatures.first()
+    {
+        let (input_signatures, input_arguments) = get_arguments_from_lit_str(signature)?;
+        if input_signatures.len() != arguments.len() {
+            return Err(SynError::new(
+                signature.span(),
+                format!(
+                    "the signature has {} arguments, but the function has {}",
+                    input_signatures.len(),
+                    arguments.len()
+                ),
+            ));
+        }
+        (input_signatures, input_arguments)
+    } else {
+        let mut input_signatures = Vec::new();
+        let mut input_arguments = Vec::new();
+        for (_, rust_type) in &arguments {
+            let signature = signature_from_type(rust_type)?;
+            input_signatures.extend(get_signatures(&signature, rust_type.span())?);
+            input_arguments.push(Argument::default());
+        }
+        (input_signatures, input_arguments)
+    };
+    // The names of the function arguments are used, if the signature has no names
+    for (argument, (argument_name, _)) in input_arguments.iter_mut().zip(arguments) {
+        argument.set_default_name(argument_name);
+    }
+
+    let (output_signatures, output_arguments) = if let Some(signature) = member.signatures.get(1) {
+        get_arguments_from_lit_str(signature)?
+    } else {
+        let ok_type = get_ok_type(method)?;
+        let signature = get_output_signatures(ok_type)?;
+        let output_signatures = get_signatures(&signature, ok_type.span())?;
+        let output_arguments = output_signatures
+            .iter()
+            .map(|_| Argument::default())
+            .collect();
+        (output_signatures, output_arguments)
+    };
+
+    let mut method = Method::new(
+        name,
+        function,
+        input_signatures,
+        input_arguments,
+        output_signatures,
+        output_arguments,
+    )?;
+    method.set_annotations(member.annotations);
+    method.set_doc(member.doc);
+    Ok(method)
+}
+
+/// Get the signature of a property from the getter or setter function.
+fn get_property_signature(
+    member: &MemberAttribute,
+    method: &ImplItemMethod,
+) -> SynResult<(String, TokenStream, TokenStream, TokenStream)> {
+    let mut signatures = if let Some(signature) = member.signatures.first() {
+        SignatureIterator::from(signature).collect::<SynResult<Vec<_>>>()?
+    } else {
+        let rust_type = match member.kind {
+            MemberKind::Get => get_ok_type(method)?,
+            _ => {
+                let arguments = get_arguments(method)?;
+                if arguments.len() != 1 {
+                    return Err(SynError::new(
+                        method.sig.inputs.span(),
+                        "excepted exactly one value argument",
+                    ));
+                }
+                arguments[0].1
+            }
+        };
+        let signature = signature_from_type(rust_type)?;
+        get_signatures(&signature, rust_type.span())?
+    };
+
+    if signatures.len() != 1 {
+        return Err(SynError::new(
+            member.span,
+            "excepted only one signature type",
+        ));
+    }
+    Ok(signatures.remove(0))
+}
+
+/// The functions of a property, which are collected from the `impl` block.
+struct PropertyFunctions {
+    name: LitStr,
+    get: Option<Ident>,
+    set: Option<Ident>,
+    signature: (String, TokenStream, TokenStream, TokenStream),
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
+}
+
+/// Parse all members of the `impl` block and remove the member attributes.
+fn parse_item_impl(
+    name: LitStr,
+    item_impl: &mut ItemImpl,
+    annotations: Vec<Annotation>,
+) -> SynResult<Interface> {
+    let mut methods = Vec::new();
+    let mut properties: Vec<PropertyFunctions> = Vec::new();
+    for impl_item in &mut item_impl.items {
+        let method = if let ImplItem::Method(method) = impl_item {
+            method
+        } else {
+            continue;
+        };
+
+        let mut members = Vec::new();
+        let mut attrs = Vec::new();
+        for attribute in method.attrs.drain(..) {
+            if let Some(member) = parse_member_attribute(&attribute)? {
+                members.push(member);
+            } else {
+                attrs.push(attribute);
+            }
+        }
+        method.attrs = attrs;
+
+        for mut member in members {
+            // `#[deprecated]` is mapped to the `org.freedesktop.DBus.Deprecated` annotation
+            if let Some(deprecated) = get_deprecated_annotation(method) {
+                add_annotations(&mut member.annotations, vec![deprecated])?;
+            }
+            // The `///` comments are used, if no documentation is defined in the attribute
+            if member.doc.is_none() {
+                member.doc = get_doc_from_attributes(&method.attrs);
+            }
+            match member.kind {
+                MemberKind::Method => methods.push(create_method(member, method)?),
+                MemberKind::Get | MemberKind::Set => {
+                    let prefix = match member.kind {
+                        MemberKind::Get => "get_",
+                        _ => "set_",
+                    };
+                    let function = method.sig.ident.clone();
+                    let property_name = get_name(&member, &function, prefix);
+                    check_member_name(&property_name, "property")?;
+                    let signature = get_property_signature(&member, method)?;
+                    let property = if let Some(property) = properties
+                        .iter_mut()
+                        .find(|property| property.name.value() == property_name.value())
+                    {
+                        if property.signature.0 != signature.0 {
+                            return Err(SynError::new(
+                                member.span,
+                                format!(
+                                    "the signature of the property is {} and not {}",
+                                    property.signature.0, signature.0
+                                ),
+                            ));
+                        }
+                        property
+                    } else {
+                        properties.push(PropertyFunctions {
+                            name: property_name,
+                            get: None,
+                            set: None,
+                            signature,
+                            annotations: Vec::new(),
+                            doc: None,
+                        });
+                        properties.last_mut().unwrap()
+                    };
+                    let (function_slot, operation) = match member.kind {
+                        MemberKind::Get => (&mut property.get, "get"),
+                        _ => (&mut property.set, "set"),
+                    };
+                    if function_slot.is_some() {
+                        return Err(SynError::new(
+                            member.span,
+                            format!("{} is defined twice", operation),
+                        ));
+                    }
+                    *function_slot = Some(function);
+                    add_annotations(&mut property.annotations, member.annotations)?;
+                    // The documentation of the first function is used for the property
+                    if property.doc.is_none() {
+                        property.doc = member.doc;
+                    }
+                }
+            }
+        }
+    }
+
+    let properties = properties
+        .into_iter()
+        .map(|property_functions| {
+            let mut property = Property::new(
+                property_functions.name,
+                property_functions.get,
+                property_functions.set,
+                property_functions.signature,
+            );
+            property.set_annotations(property_functions.annotations)?;
+            property.set_doc(property_functions.doc);
+            Ok(property)
+        })
+        .collect::<SynResult<_>>()?;
+    // The `///` comments of the `impl` block are the documentation of the interface
+    let doc = get_doc_from_attributes(&item_impl.attrs);
+    Interface::new(name, methods, properties, Vec::new(), annotations, doc)
+}
+
+/// Try to create the `dbus_async::Handler` implementation from an `impl` block.
+pub(super) fn try_interface(name: LitStr, mut item_impl: ItemImpl) -> SynResult<TokenStream> {
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Err(SynError::new(
+            path.span(),
+            "excepted an inherent impl block",
+        ));
+    }
+
+    // Remove the `#[dbus(...)]` and the `#[annotation(...)]` attributes, because they are only
+    // known by this macro
+    let mut dbus = None;
+    let mut annotations = Vec::new();
+    let mut attrs = Vec::new();
+    for attribute in item_impl.attrs.drain(..) {
+        if attribute.path.is_ident("annotation") {
+            let meta = parse_meta(&attribute)?;
+            annotations.push(Annotation::try_from(get_meta_list_from_meta(&meta)?)?);
+            continue;
+        }
+        if attribute.path.is_ident("dbus") {
+            if dbus.is_some() {
+                return Err(SynError::new(
+                    attribute.span(),
+                    "Dbus is defined multiple times",
+                ));
+            }
+            let meta = parse_meta(&attribute)?;
+            dbus = Some(parse_dbus(get_meta_list_from_meta(&meta)?)?);
+        } else {
+            attrs.push(attribute);
+        }
+    }
+    item_impl.attrs = attrs;
+
+    let interface = parse_item_impl(name, &mut item_impl, annotations)?;
+    let (crate_path, export_xml) = dbus.unwrap_or_default();
+    let interfaces = vec![interface];
+    if let Some(export_xml) = export_xml {
+        export_interfaces(&export_xml, &interfaces)?;
+    }
+    let object = Object::new(interfaces, true, true, None, Vec::new(), None, crate_path);
+    object.check_standard_interfaces()?;
+    let self_type = &item_impl.self_ty;
+    let self_type = quote! { #self_type };
+    let handler_code = object.create_handler_code(&self_type, item_impl.generics.clone());
+    Ok(quote! {
+        #item_impl
+
+        #handler_code
+    })
+}
@@ -0,0 +1,463 @@
+use crate::annotation::Annotation;
+use crate::argument::{get_ident_from_xml_name, Argument};
+use crate::crate_path::{parse_dbus, replace_crate_aliases};
+use crate::helper::{
+    get_lit_str_from_lit, get_lit_str_from_option_nested_meta, get_meta_list_from_meta,
+    get_named_arguments, get_required_argument, is_named_syntax, parse_meta, to_snake_case,
+};
+use crate::interface::Interface;
+use crate::method::Method;
+use crate::name::{
+    check_annotation_name, check_duplicates, check_member_name, STANDARD_INTERFACES,
+};
+use crate::property::{get_property_signature, EmitsChanged, Property, EMITS_CHANGED_SIGNAL};
+use crate::signal::Signal;
+use crate::xml::{parse_xml, Element};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::convert::TryFrom;
+use std::env::var_os;
+use std::fmt::Display;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use syn::spanned::Spanned;
+use syn::{Error as SynError, ItemTrait, Lit, LitStr, MetaList, Result as SynResult, TraitItem};
+
+/// The signature, the Rust type and the conversion code of an argument.
+type ArgumentSignature = (String, TokenStream, TokenStream, TokenStream);
+
+/// An introspection XML file, which defines the interfaces of an object:
+/// `interface_xml("file.xml")` or
+/// `interface_xml(file = "file.xml", interface = "org.example.interface")`.
+/// The path of the file is relative to the directory of the `Cargo.toml`.
+pub(super) struct InterfaceXml {
+    file: LitStr,
+    /// Only this interface of the file is used. By default, all interfaces are used.
+    interface: Option<LitStr>,
+    /// The names of the handler functions, which are used to find collisions.
+    functions: Vec<LitStr>,
+}
+
+impl InterfaceXml {
+    /// Parse the named-key syntax: `(file = "file.xml", interface = "org.example.interface")`.
+    fn try_from_named(meta_list: &MetaList) -> SynResult<InterfaceXml> {
+        let mut file = None;
+        let mut interface = None;
+        for (key, lit) in get_named_arguments(meta_list, &["file", "interface"])? {
+            match key.to_string().as_ref() {
+                "file" => file = Some(get_lit_str_from_lit(lit)?),
+                "interface" => interface = Some(get_lit_str_from_lit(lit)?),
+                _ => unreachable!(),
+            }
+        }
+
+        let file = get_required_argument(meta_list, "file", file)?;
+        Ok(InterfaceXml {
+            file,
+            interface,
+            functions: Vec::new(),
+        })
+    }
+
+    /// Parse the positional syntax: `("file.xml")`.
+    fn try_from_positional(meta_list: &MetaList) -> SynResult<InterfaceXml> {
+        let nested_iter = &mut meta_list.nested.iter();
+        let file = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        if nested_iter.next().is_some() {
+            return Err(SynError::new(meta_list.span(), "too many arguments"));
+        }
+        Ok(InterfaceXml {
+            file,
+            interface: None,
+            functions: Vec::new(),
+        })
+    }
+
+    /// Get the absolute path of the file.
+    fn get_path(&self) -> PathBuf {
+        let path = PathBuf::from(self.file.value());
+        match var_os("CARGO_MANIFEST_DIR") {
+            Some(dir) => PathBuf::from(dir).join(path),
+            None => path,
+        }
+    }
+
+    /// Create the code, which includes the file. Therefore, the crate is rebuilt if the file
+    /// changes.
+    pub(super) fn create_include_code(&self) -> TokenStream {
+        let path = self.get_path().display().to_string();
+        quote! {
+            const _: &str = ::std::include_str!(#path);
+        }
+    }
+
+    /// Create an error, which points at the file and contains the line of the element.
+    fn error<T: Display>(&self, element: &Element, message: T) -> SynError {
+        SynError::new(
+            self.file.span(),
+            format!("{}:{}: {}", self.file.value(), element.get_line(), message),
+        )
+    }
+
+    /// Add the line of the element to an error of the checks of the names and the signatures.
+    fn with_line<T>(&self, element: &Element, result: SynResult<T>) -> SynResult<T> {
+        result.map_err(|e| self.error(element, e))
+    }
+
+    fn create_lit_str(&self, value: &str) -> LitStr {
+        LitStr::new(value, self.file.span())
+    }
+
+    fn get_required_attribute<'a>(&self, element: &'a Element, name: &str) -> SynResult<&'a str> {
+        element.get_attribute(name).ok_or_else(|| {
+            self.error(
+                element,
+                format!(
+                    "the element <{}> has no attribute \"{}\"",
+                    element.get_name(),
+                    name
+                ),
+            )
+        })
+    }
+
+    /// Get the handler function of the method or the property (e.g. `get_` for the get function).
+    fn get_function(&mut self, element: &Element, prefix: &str, name: &str) -> SynResult<Ident> {
+        let function = format!("{}{}", prefix, to_snake_case(name));
+        let function = get_ident_from_xml_name(&function, self.file.span(), element.get_name());
+        let function = self.with_line(element, function)?;
+        self.functions
+            .push(self.create_lit_str(&function.to_string()));
+        Ok(function)
+    }
+
+    fn get_doc(&self, element: &Element) -> Option<LitStr> {
+        match element.get_doc() {
+            Some(doc) if !doc.is_empty() => Some(self.create_lit_str(doc)),
+            _ => None,
+        }
+    }
+
+    fn unknown_element(&self, parent: &Element, element: &Element) -> SynError {
+        self.error(
+            element,
+            format!(
+                "unknown element <{}> in <{}>",
+                element.get_name(),
+                parent.get_name()
+            ),
+        )
+    }
+
+    /// Parse `<annotation name="org.example.Name" value="value"/>`.
+    fn get_annotation(&self, element: &Element) -> SynResult<Annotation> {
+        let name = self.get_required_attribute(element, "name")?;
+        let name = self.create_lit_str(name);
+        self.with_line(element, check_annotation_name(&name))?;
+        let value = self.get_required_attribute(element, "value")?;
+        Ok(Annotation::new(name, self.create_lit_str(value)))
+    }
+
+    /// Parse `<arg type="s" name="name" direction="in"/>`. The name and the direction are optional.
+    fn get_argument(&self, element: &Element) -> SynResult<(ArgumentSignature, Argument)> {
+        let signature = self.get_required_attribute(element, "type")?;
+        let signature = get_property_signature(&self.create_lit_str(signature));
+        let signature = self.with_line(element, signature)?;
+
+        let mut annotations = Vec::new();
+        for child in element.get_children() {
+            match child.get_name() {
+                "annotation" => annotations.push(self.get_annotation(child)?),
+                _ => return Err(self.unknown_element(element, child)),
+            }
+        }
+        let doc = self.get_doc(element);
+
+        let argument = match element.get_attribute("name") {
+            Some(name) => {
+                let argument = Argument::from_xml(name, self.file.span(), annotations, doc);
+                self.with_line(element, argument)?
+            }
+            None => Argument::from_xml_unnamed(annotations, doc),
+        };
+        Ok((signature, argument))
+    }
+
+    /// Parse `<method name="Name">` with the input and the output arguments.
+    fn get_method(&mut self, element: &Element) -> SynResult<Method> {
+        let name = self.get_required_attribute(element, "name")?;
+        let name_lit_str = self.create_lit_str(name);
+        self.with_line(element, check_member_name(&name_lit_str, "method"))?;
+        let function = self.get_function(element, "", name)?;
+
+        let mut input = (Vec::new(), Vec::new());
+        let mut output = (Vec::new(), Vec::new());
+        let mut annotations = Vec::new();
+        for child in element.get_children() {
+            match child.get_name() {
+                "arg" => {
+                    let (signature, argument) = self.get_argument(child)?;
+                    match child.get_attribute("direction").unwrap_or("in") {
+                        "in" => {
+                            input.0.push(signature);
+                            input.1.push(argument);
+                        }
+                        "out" => {
+                            output.0.push(signature);
+                            output.1.push(argument);
+                        }
+                        direction => {
+                            return Err(self.error(
+                                child,
+                                format!("expected \"in\" or \"out\" got {:?}", direction),
+                            ))
+                        }
+                    }
+                }
+                "annotation" => annotations.push(self.get_annotation(child)?),
+                _ => return Err(self.unknown_element(element, child)),
+            }
+        }
+
+        let method = Method::new(name_lit_str, function, input.0, input.1, output.0, output.1);
+        let mut method = self.with_line(element, method)?;
+        method.set_annotations(annotations);
+        method.set_doc(self.get_doc(element));
+        Ok(method)
+    }
+
+    /// Parse `<property name="Name" type="s" access="readwrite">`. The property is accessed by the
+    /// functions `get_<name>` and `set_<name>`.
+    fn get_property(&mut self, element: &Element) -> SynResult<Property> {
+        let name = self.get_required_attribute(element, "name")?;
+        let name_lit_str = self.create_lit_str(name);
+        self.with_line(element, check_member_name(&name_lit_str, "property"))?;
+        let signature = self.get_required_attribute(element, "type")?;
+        let signature = get_property_signature(&self.create_lit_str(signature));
+        let signature = self.with_line(element, signature)?;
+
+        let (get, set) = match self.get_required_attribute(element, "access")? {
+            "read" => (Some(self.get_function(element, "get_", name)?), None),
+            "write" => (None, Some(self.get_function(element, "set_", name)?)),
+            "readwrite" => (
+                Some(self.get_function(element, "get_", name)?),
+                Some(self.get_function(element, "set_", name)?),
+            ),
+            access => {
+                return Err(self.error(
+                    element,
+                    format!(
+                        "expected \"read\", \"write\" or \"readwrite\" got {:?}",
+                        access
+                    ),
+                ))
+            }
+        };
+
+        let mut property = Property::new(name_lit_str, get, set, signature);
+        let mut annotations = Vec::new();
+        for child in element.get_children() {
+            match child.get_name() {
+                "annotation" => {
+                    let annotation = self.get_annotation(child)?;
+                    // The annotation is used for the emit function of the property
+                    if annotation.get_name().value() == EMITS_CHANGED_SIGNAL {
+                        let value = Lit::Str(annotation.get_value().clone());
+                        let emits_changed = EmitsChanged::try_from(&value);
+                        property.set_emits_changed(self.with_line(child, emits_changed)?);
+                    } else {
+                        annotations.push(annotation);
+                    }
+                }
+                _ => return Err(self.unknown_element(element, child)),
+            }
+        }
+        self.with_line(element, property.set_annotations(annotations))?;
+        property.set_doc(self.get_doc(element));
+        Ok(property)
+    }
+
+    /// Parse `<signal name="Name">` with the arguments.
+    fn get_signal(&self, element: &Element) -> SynResult<Signal> {
+        let name = self.get_required_attribute(element, "name")?;
+
+        let mut signatures = Vec::new();
+        let mut arguments = Vec::new();
+        let mut annotations = Vec::new();
+        for child in element.get_children() {
+            match child.get_name() {
+                "arg" => {
+                    if let Some(direction) = child.get_attribute("direction") {
+                        if direction != "out" {
+                            return Err(
+                                self.error(child, format!("expected \"out\" got {:?}", direction))
+                            );
+                        }
+                    }
+                    let (signature, argument) = self.get_argument(child)?;
+                    signatures.push(signature);
+                    arguments.push(argument);
+                }
+                "annotation" => annotations.push(self.get_annotation(child)?),
+                _ => return Err(self.unknown_element(element, child)),
+            }
+        }
+
+        let signal = Signal::new(self.create_lit_str(name), signatures, arguments);
+        let mut signal = self.with_line(element, signal)?;
+        signal.set_annotations(annotations);
+        signal.set_doc(self.get_doc(element));
+        Ok(signal)
+    }
+
+    /// Parse `<interface name="org.example.interface">` with the members.
+    fn get_interface(&mut self, element: &Element, name: &str) -> SynResult<Interface> {
+        let mut methods = Vec::new();
+        let mut properties = Vec::new();
+        let mut signals = Vec::new();
+        let mut annotations = Vec::new();
+        for child in element.get_children() {
+            match child.get_name() {
+                "method" => methods.push(self.get_method(child)?),
+                "property" => properties.push(self.get_property(child)?),
+                "signal" => signals.push(self.get_signal(child)?),
+                "annotation" => annotations.push(self.get_annotation(child)?),
+                _ => return Err(self.unknown_element(element, child)),
+            }
+        }
+
+        let interface = Interface::new(
+            self.create_lit_str(name),
+            methods,
+            properties,
+            signals,
+            annotations,
+            self.get_doc(element),
+        );
+        self.with_line(element, interface)
+    }
+
+    /// Read the file and create the interfaces. The standard interfaces (e.g.
+    /// `org.freedesktop.DBus.Properties`) are skipped, because they are generated.
+    pub(super) fn read_interfaces(&mut self) -> SynResult<Vec<Interface>> {
+        let path = self.get_path();
+        let xml = read_to_string(&path).map_err(|e| {
+            SynError::new(
+                self.file.span(),
+                format!("could not read {}: {}", path.display(), e),
+            )
+        })?;
+        let node = parse_xml(&xml).map_err(|e| {
+            SynError::new(self.file.span(), format!("{}: {}", self.file.value(), e))
+        })?;
+        if node.get_name() != "node" {
+            return Err(self.error(&node, "expected the root element <node>"));
+        }
+
+        let mut interfaces = Vec::new();
+        for child in node.get_children() {
+            match child.get_name() {
+                "interface" => {
+                    let name = self.get_required_attribute(child, "name")?;
+                    if STANDARD_INTERFACES.contains(&name) {
+                        continue;
+                    }
+                    if let Some(interface) = &self.interface {
+                        if interface.value() != name {
+                            continue;
+                        }
+                    }
+                    interfaces.push(self.get_interface(child, name)?);
+                }
+                // The child objects are not part of the interfaces
+                "node" => {}
+                _ => return Err(self.unknown_element(&node, child)),
+            }
+        }
+
+        if let Some(interface) = &self.interface {
+            if interfaces.is_empty() {
+                return Err(SynError::new(
+                    interface.span(),
+                    format!(
+                        "the interface {:?} is not defined in {}",
+                        interface.value(),
+                        self.file.value()
+                    ),
+                ));
+            }
+        } else if interfaces.is_empty() {
+            return Err(SynError::new(
+                self.file.span(),
+                format!("no interface is defined in {}", self.file.value()),
+            ));
+        }
+        check_duplicates(&self.functions, "handler function")?;
+        Ok(interfaces)
+    }
+}
+
+impl TryFrom<&MetaList> for InterfaceXml {
+    type Error = SynError;
+
+    fn try_from(meta_list: &MetaList) -> Result<Self, Self::Error> {
+        if is_named_syntax(meta_list) {
+            InterfaceXml::try_from_named(meta_list)
+        } else {
+            InterfaceXml::try_from_positional(meta_list)
+        }
+    }
+}
+
+/// Try to add the declarations of the handler functions of the interfaces of an introspection XML
+/// file to a trait.
+pub(super) fn try_interface_trait(
+    meta_list: MetaList,
+    mut item_trait: ItemTrait,
+) -> SynResult<TokenStream> {
+    // Remove the `#[dbus(...)]` attribute, because it is only known by this macro
+    let mut crate_path = None;
+    let mut attrs = Vec::new();
+    for attribute in item_trait.attrs.drain(..) {
+        if attribute.path.is_ident("dbus") {
+            if crate_path.is_some() {
+                return Err(SynError::new(
+                    attribute.span(),
+                    "Dbus is defined multiple times",
+                ));
+            }
+            let meta = parse_meta(&attribute)?;
+            let (path, export_xml) = parse_dbus(get_meta_list_from_meta(&meta)?)?;
+            if export_xml.is_some() {
+                return Err(SynError::new(
+                    attribute.span(),
+                    "export_xml can only be used for an object",
+                ));
+            }
+            crate_path = Some(path);
+        } else {
+            attrs.push(attribute);
+        }
+    }
+    item_trait.attrs = attrs;
+    let crate_path = crate_path.flatten();
+
+    let mut interface_xml = InterfaceXml::try_from(&meta_list)?;
+    let interfaces = interface_xml.read_interfaces()?;
+    for function in interfaces
+        .iter()
+        .flat_map(Interface::create_trait_functions_code)
+    {
+        let function = replace_crate_aliases(function, crate_path.as_ref());
+        item_trait.items.push(syn::parse2::<TraitItem>(function)?);
+    }
+
+    let async_trait = replace_crate_aliases(quote! { __async_trait }, crate_path.as_ref());
+    let include_code = interface_xml.create_include_code();
+    Ok(quote! {
+        #[#async_trait::async_trait]
+        #item_trait
+
+        #include_code
+    })
+}
@@ -2,17 +2,22 @@ use crate::code::{
     check_if_no_signature_from_header, check_if_no_value_from_body_iter,
     create_return_msg_from_header, get_member_from_header, unknown_member_from_header,
 };
+use crate::delegate::Delegate;
 use crate::helper::{get_ident_from_path, get_lit_bool_from_option_nested_meta};
 use crate::interface::Interface;
+use crate::object_manager::OBJECT_MANAGER_XML;
+use crate::peer::PEER_XML;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::spanned::Spanned;
 use syn::{Error as SynError, MetaList, Result as SynResult};
 
-static START_XML: &str = r#"
+pub(super) static START_XML: &str = r#"
 <!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
                       "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
-<node>
+<node>"#;
+
+static INTROSPECTABLE_XML: &str = r#"
    <interface name="org.freedesktop.DBus.Introspectable">
      <method name="Introspect">
        <arg type="s" name="xml_data" direction="out"/>
@@ -48,20 +53,86 @@ pub(super) trait Introspectable {
     fn to_introspect(&self, xml: &mut String);
 }
 
-pub(super) fn create_introspectable_code(
+/// Escape the characters, which are not allowed in an XML attribute value.
+pub(super) fn escape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => result += "&amp;",
+            '<' => result += "&lt;",
+            '>' => result += "&gt;",
+            '"' => result += "&quot;",
+            '\'' => result += "&apos;",
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Create the code, which appends the introspection XML of the delegates.
+/// The standard interfaces are already part of the XML of the object.
+fn create_delegates_introspect_code(delegates: &[Delegate]) -> TokenStream {
+    let delegates_introspect = delegates
+        .iter()
+        .map(|delegate| delegate.create_introspect_code(&quote! { &mut xml }));
+    quote! {
+        #(#delegates_introspect)*
+    }
+}
+
+/// Create the functions, which return the introspection XML of the object without the child
+/// nodes. The XML is also available if the `org.freedesktop.DBus.Introspectable` interface is not
+/// implemented, e.g. to export it.
+pub(super) fn create_introspection_xml_code(
     interfaces: &[Interface],
+    have_introspectable: bool,
     have_properties: bool,
+    have_peer: bool,
+    have_object_manager: bool,
+    delegates: &[Delegate],
 ) -> TokenStream {
     let mut xml = START_XML.to_string();
 
+    if have_introspectable {
+        xml += INTROSPECTABLE_XML;
+    }
+
     if have_properties {
         xml += PROPERTIES_XML;
     }
 
+    if have_peer {
+        xml += PEER_XML;
+    }
+
+    if have_object_manager {
+        xml += OBJECT_MANAGER_XML;
+    }
+
     for interface in interfaces {
         interface.to_introspect(&mut xml);
     }
 
+    let delegates_introspect = create_delegates_introspect_code(delegates);
+    quote! {
+        /// Get the introspection XML of the object without the child nodes and without the
+        /// closing tag.
+        fn __dbus_introspect_node() -> ::std::string::String {
+            let mut xml = #xml.to_string();
+            #delegates_introspect
+            xml
+        }
+
+        /// Get the introspection XML of the object without the child nodes.
+        pub fn introspection_xml() -> ::std::string::String {
+            let mut xml = Self::__dbus_introspect_node();
+            xml += "</node>";
+            xml
+        }
+    }
+}
+
+pub(super) fn create_introspectable_code() -> TokenStream {
     let check_if_no_signature_from_header = check_if_no_signature_from_header();
     let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
     let get_member_from_header = get_member_from_header();
@@ -73,16 +144,16 @@ pub(super) fn create_introspectable_code(
                  "Introspect" => {
                      #check_if_no_signature_from_header;
                      #check_if_no_value_from_body_iter;
-                     let mut xml = #xml.to_string();
-                     if let Some(path) = header.get_path() {
+                     let mut xml = Self::__dbus_introspect_node();
+                     if let ::std::option::Option::Some(path) = header.get_path() {
                         let list = dbus.list_method_call(path.clone()).await?;
                         for l in list {
-                            xml += &format!("  <node name=\"{}\"/>\n", l);
+                            xml += &::std::format!("  <node name=\"{}\"/>\n", l);
                         }
                      }
                      xml += "</node>";
                      let mut msg = #create_return_msg_from_header;
-                     msg.add_value(dbus_message_parser::Value::String(xml));
+                     msg.add_value(__dbus_message_parser::Value::String(xml));
                      return dbus.send(msg);
                  }
                  _ => #unknown_member_from_header
@@ -108,3 +179,19 @@ pub(super) fn parse_introspectable(meta_list: &MetaList) -> SynResult<bool> {
     }
     Ok(lit_bool.value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::escape_xml;
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_xml("org.example.Name"), "org.example.Name");
+        assert_eq!(
+            escape_xml(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+        );
+        assert_eq!(escape_xml("&amp;"), "&amp;amp;");
+        assert_eq!(escape_xml(""), "");
+    }
+}
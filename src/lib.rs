@@ -1,37 +1,124 @@
 #![recursion_limit = "256"]
 extern crate proc_macro;
 
+mod annotation;
+mod argument;
+mod check;
 mod code;
+mod crate_path;
+mod delegate;
+mod doc;
+mod export;
+mod fallback;
 mod helper;
 mod interface;
+mod interface_impl;
+mod interface_xml;
 mod introspectable;
 mod method;
+mod name;
+mod object;
+mod object_manager;
+mod peer;
 mod properties;
 mod property;
 mod signal;
 mod signature;
+mod xml;
 
-use crate::code::{get_interface_from_header, unknown_interface_from_header};
-use crate::helper::{get_ident_from_path, get_meta_list_from_meta};
+use crate::crate_path::parse_dbus;
+use crate::delegate::parse_delegates;
+use crate::export::export_interfaces;
+use crate::fallback::parse_fallback;
+use crate::helper::{
+    did_you_mean, get_ident_from_path, get_meta_list_from_meta, get_struct_fields, parse_meta,
+};
 use crate::interface::Interface;
-use crate::introspectable::{create_introspectable_code, parse_introspectable};
-use crate::properties::create_properties_code;
+use crate::interface_impl::try_interface;
+use crate::interface_xml::{try_interface_trait, InterfaceXml};
+use crate::introspectable::parse_introspectable;
+use crate::name::check_duplicates;
+use crate::object::Object;
+use crate::object_manager::parse_object_manager;
+use crate::peer::parse_peer;
 use proc_macro::TokenStream;
 use quote::quote;
 use std::convert::TryFrom;
-use syn::{parse_macro_input, DeriveInput, Error as SynError, Result as SynResult};
+use syn::{
+    parse_macro_input, Attribute, DeriveInput, Error as SynError, ItemImpl, ItemTrait, LitStr,
+    MetaList, Result as SynResult,
+};
+
+/// The attributes of the struct, which are handled by the derive macro.
+const ATTRIBUTES: [&str; 6] = [
+    "interface",
+    "interface_xml",
+    "introspectable",
+    "peer",
+    "fallback",
+    "dbus",
+];
+
+/// Check if an attribute, which is not handled by the derive macro, is a misspelled attribute of
+/// the derive macro (e.g. `#[interfase(...)]`). Other attributes like `///` comments are ignored.
+fn check_misspelled_attribute(attribute: &Attribute) -> SynResult<()> {
+    if let Some(ident) = attribute.path.get_ident() {
+        let name = ident.to_string();
+        let suggestion = did_you_mean(&name, &ATTRIBUTES);
+        if !suggestion.is_empty() {
+            return Err(SynError::new(
+                ident.span(),
+                format!("Unknown attribute: {}{}", name, suggestion),
+            ));
+        }
+    }
+    Ok(())
+}
 
 /// Try to derive
 fn try_derive(ast: DeriveInput) -> SynResult<TokenStream> {
+    let delegates = parse_delegates(&ast)?;
+    let object_manager = parse_object_manager(&ast)?;
     let struct_name = ast.ident;
+    let generics = ast.generics;
+    let (_, ty_generics, _) = generics.split_for_impl();
     let mut introspectable = None;
+    let mut peer = None;
+    let mut fallback = None;
+    let mut dbus = None;
     let mut interfaces = Vec::new();
+    let mut include_codes = Vec::new();
     for attribute in ast.attrs {
-        let meta = attribute.parse_meta()?;
+        // Other attributes (e.g. `///` comments) are not handled by this macro
+        if !ATTRIBUTES.iter().any(|name| attribute.path.is_ident(name)) {
+            check_misspelled_attribute(&attribute)?;
+            continue;
+        }
+        let meta = parse_meta(&attribute)?;
+        // The fallback can also be given as `#[fallback = function]`
+        if attribute.path.is_ident("fallback") {
+            if fallback.is_some() {
+                return Err(SynError::new(
+                    get_ident_from_path(&attribute.path)?.span(),
+                    "Fallback is defined multiple times",
+                ));
+            }
+            fallback = Some(parse_fallback(&meta)?);
+            continue;
+        }
         let meta_list = get_meta_list_from_meta(&meta)?;
         let meta_list_type = get_ident_from_path(&meta_list.path)?;
         match meta_list_type.to_string().as_ref() {
-            "interface" => interfaces.push(Interface::try_from(meta_list)?),
+            "interface" => {
+                let mut interface = Interface::try_from(meta_list)?;
+                interface.infer_signatures(&get_struct_fields(&ast.data))?;
+                interfaces.push(interface);
+            }
+            "interface_xml" => {
+                let mut interface_xml = InterfaceXml::try_from(meta_list)?;
+                interfaces.extend(interface_xml.read_interfaces()?);
+                include_codes.push(interface_xml.create_include_code());
+            }
             "introspectable" => {
                 if introspectable.is_some() {
                     return Err(SynError::new(
@@ -43,64 +130,85 @@ fn try_derive(ast: DeriveInput) -> SynResult<TokenStream> {
                     introspectable = Some(boolean);
                 }
             }
-            attribute => {
-                return Err(SynError::new(
-                    meta_list_type.span(),
-                    format!("Unknown attribute: {}", attribute),
-                ))
+            "peer" => {
+                if peer.is_some() {
+                    return Err(SynError::new(
+                        meta_list_type.span(),
+                        "Peer is defined multiple times",
+                    ));
+                } else {
+                    let boolean = parse_peer(meta_list)?;
+                    peer = Some(boolean);
+                }
+            }
+            "dbus" => {
+                if dbus.is_some() {
+                    return Err(SynError::new(
+                        meta_list_type.span(),
+                        "Dbus is defined multiple times",
+                    ));
+                } else {
+                    dbus = Some(parse_dbus(meta_list)?);
+                }
             }
+            _ => unreachable!(),
         }
     }
 
-    let introspectable = if let Some(introspectable) = introspectable {
-        introspectable
-    } else {
-        true
-    };
-
-    let mut interfaces_code = Vec::new();
-
-    let have_properties = match create_properties_code(&interfaces) {
-        Some(code) => {
-            interfaces_code.push(code);
-            true
-        }
-        None => false,
-    };
+    check_duplicates(interfaces.iter().map(Interface::get_name), "interface")?;
+    let emit_function_names: Vec<LitStr> = interfaces
+        .iter()
+        .flat_map(Interface::get_emit_function_names)
+        .collect();
+    check_duplicates(&emit_function_names, "emit function")?;
+    let introspection_const_names: Vec<LitStr> = interfaces
+        .iter()
+        .map(Interface::get_introspection_const_name)
+        .collect();
+    check_duplicates(&introspection_const_names, "introspection constant")?;
 
-    if introspectable {
-        interfaces_code.push(create_introspectable_code(&interfaces, have_properties));
+    let (crate_path, export_xml) = dbus.unwrap_or_default();
+    if let Some(export_xml) = export_xml {
+        export_interfaces(&export_xml, &interfaces)?;
     }
 
-    for interface in &interfaces {
-        if let Some(code) = interface.create_methods_code() {
-            interfaces_code.push(code);
-        }
-    }
+    let introspectable = introspectable.unwrap_or(true);
+    let peer = peer.unwrap_or(true);
 
-    let get_interface_from_header = get_interface_from_header();
-    let unknown_interface_from_header = unknown_interface_from_header();
+    let object = Object::new(
+        interfaces,
+        introspectable,
+        peer,
+        fallback,
+        delegates,
+        object_manager,
+        crate_path,
+    );
+    object.check_standard_interfaces()?;
+    let self_type = quote! { #struct_name #ty_generics };
+    let code = object.create_handler_code(&self_type, generics.clone());
     let code = quote! {
-        #[async_trait::async_trait]
-        impl dbus_async::Handler for #struct_name {
-            async fn handle(&mut self, dbus: &dbus_async::DBus, msg: dbus_message_parser::Message) -> dbus_async::DBusResult<()> {
-                if msg.get_type() != dbus_message_parser::MessageType::MethodCall {
-                    return Ok(())
-                }
-                let (header, body) = msg.split();
-                let mut body_iter = body.into_iter();
-                match #get_interface_from_header {
-                    #(#interfaces_code)*
-                    _ => #unknown_interface_from_header
-                }
-            }
-        }
+        #code
+
+        #(#include_codes)*
     };
     Ok(code.into())
 }
 
 /// The derive method.
-#[proc_macro_derive(Handler, attributes(interface, introspectable))]
+#[proc_macro_derive(
+    Handler,
+    attributes(
+        interface,
+        interface_xml,
+        introspectable,
+        peer,
+        fallback,
+        delegate,
+        object_manager,
+        dbus
+    )
+)]
 pub fn derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     match try_derive(ast) {
@@ -108,3 +216,35 @@ pub fn derive(input: TokenStream) -> TokenStream {
         Err(e) => e.to_compile_error().into(),
     }
 }
+
+/// The attribute macro for `impl` blocks.
+/// It derives the interface from the functions, which have a `#[method]`, `#[get]` or `#[set]`
+/// attribute, and implements `dbus_async::Handler` for the type.
+/// Because the `Handler` is implemented, only one `impl` block of a type can have this attribute.
+/// An object with multiple interfaces has to use the derive macro.
+#[proc_macro_attribute]
+pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let name = parse_macro_input!(attr as LitStr);
+    let item_impl = parse_macro_input!(item as ItemImpl);
+    match try_interface(name, item_impl) {
+        Ok(token) => token.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// The attribute macro for traits.
+/// It declares the handler functions of the interfaces of an introspection XML file in the trait
+/// (e.g. `#[interface_trait("org.example.interface.xml")]`).
+#[proc_macro_attribute]
+pub fn interface_trait(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let meta_list = match syn::parse2::<MetaList>(quote! { interface_trait(#attr) }) {
+        Ok(meta_list) => meta_list,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let item_trait = parse_macro_input!(item as ItemTrait);
+    match try_interface_trait(meta_list, item_trait) {
+        Ok(token) => token.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
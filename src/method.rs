@@ -1,26 +1,79 @@
+use crate::annotation::{annotations_to_introspect, split_annotations, Annotation};
+use crate::argument::{
+    check_argument_names, get_arguments_from_lit_str, get_arguments_from_nested_meta, Argument,
+};
+use crate::check::{
+    create_argument_check, create_output_check, get_argument_check_function,
+    get_output_check_function, rust_type_to_string,
+};
 use crate::code::{
     check_if_no_value_from_body_iter, check_result, check_signature_from_header,
     create_return_msg_from_header, get_value_from_body_iter,
 };
+use crate::doc::{doc_to_introspect, split_doc};
 use crate::helper::{
-    get_ident_from_option_nested_meta, get_ident_from_path, get_lit_str_from_option_nested_meta,
-    get_signatures_from_nested_meta,
+    get_ident_from_lit, get_ident_from_option_nested_meta, get_ident_from_path,
+    get_lit_str_from_lit, get_lit_str_from_option_nested_meta, get_named_arguments,
+    get_required_argument, is_named_syntax,
 };
 use crate::introspectable::Introspectable;
+use crate::name::check_member_name;
 use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
+use quote::{quote, quote_spanned};
 use std::convert::TryFrom;
 use syn::spanned::Spanned;
-use syn::{Error as SynError, Index, LitStr, MetaList};
+use syn::{Error as SynError, Index, LitStr, MetaList, Result as SynResult};
 
 pub(crate) struct Method {
     name: LitStr,
     function: Ident,
     input_signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+    input_arguments: Vec<Argument>,
     output_signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+    output_arguments: Vec<Argument>,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
 }
 
+/// The names, which are used by the generated code and cannot be used for input arguments.
+const RESERVED_NAMES: [&str; 4] = ["self", "dbus", "header", "body_iter"];
+
 impl Method {
+    pub(super) fn new(
+        name: LitStr,
+        function: Ident,
+        input_signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+        input_arguments: Vec<Argument>,
+        output_signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+        output_arguments: Vec<Argument>,
+    ) -> SynResult<Method> {
+        // The default names of the arguments are counted across the input and output arguments
+        check_argument_names(&input_arguments, 0, &RESERVED_NAMES)?;
+        check_argument_names(&output_arguments, input_arguments.len(), &[])?;
+        Ok(Method {
+            name,
+            function,
+            input_signatures,
+            input_arguments,
+            output_signatures,
+            output_arguments,
+            annotations: Vec::new(),
+            doc: None,
+        })
+    }
+
+    pub(super) fn get_name(&self) -> &LitStr {
+        &self.name
+    }
+
+    pub(super) fn set_annotations(&mut self, annotations: Vec<Annotation>) {
+        self.annotations = annotations;
+    }
+
+    pub(super) fn set_doc(&mut self, doc: Option<LitStr>) {
+        self.doc = doc;
+    }
+
     fn get_input_signature(&self) -> String {
         let mut result = String::new();
         for (signature, _, _, _) in &self.input_signatures {
@@ -34,16 +87,132 @@ impl Method {
         check_signature_from_header(&input_signature)
     }
 
+    /// Get the Rust type of the return value. Multiple output arguments are returned as a tuple.
+    fn get_output_rust_type(&self) -> TokenStream {
+        if self.output_signatures.len() == 1 {
+            self.output_signatures[0].1.clone()
+        } else {
+            let rust_types = self
+                .output_signatures
+                .iter()
+                .map(|(_, rust_type, _, _)| rust_type);
+            quote! { (#(#rust_types),*) }
+        }
+    }
+
+    /// Create the declaration of the handler function in the trait of the interface `interface`.
+    pub(super) fn create_trait_function_code(&self, interface: &LitStr) -> TokenStream {
+        let function = &self.function;
+        let mut docs = vec![format!(
+            "The method `{}` of the interface `{}`.",
+            self.name.value(),
+            interface.value()
+        )];
+        if let Some(doc) = &self.doc {
+            docs.insert(0, String::new());
+            docs.insert(0, format!(" {}", doc.value()));
+        }
+        let arguments = self
+            .input_signatures
+            .iter()
+            .zip(&self.input_arguments)
+            .enumerate()
+            .map(|(i, ((_, rust_type, _, _), argument))| {
+                let argument = argument.get_ident(i, self.name.span());
+                quote! { #argument: #rust_type }
+            });
+        let output_rust_type = self.get_output_rust_type();
+        // The number of arguments is defined by the interface
+        quote! {
+            #(#[doc = #docs])*
+            #[allow(clippy::too_many_arguments)]
+            async fn #function(
+                &mut self,
+                dbus: &__dbus_async::DBus,
+                msg_header: &__dbus_message_parser::MessageHeader,
+                #(#arguments),*
+            ) -> ::std::result::Result<#output_rust_type, (__dbus_message_parser::Error, ::std::string::String)>;
+        }
+    }
+
+    /// Create the checks, if the function has the types of the signatures.
+    /// It returns the names of the check functions and the items of the checks.
+    pub(super) fn create_check_code(&self) -> Vec<(Ident, TokenStream)> {
+        let name = self.name.value();
+        let span = self.name.span();
+        let function = &self.function;
+
+        let mut checks = Vec::new();
+        for (i, (signature, rust_type, _, _)) in self.input_signatures.iter().enumerate() {
+            let message = format!(
+                "argument {} of `{}` is `{}` ({}), but `{}` takes `{{Self}}`",
+                i + 1,
+                name,
+                signature,
+                rust_type_to_string(rust_type),
+                function
+            );
+            checks.push(create_argument_check(
+                function, i, rust_type, &message, span,
+            ));
+        }
+
+        let output_signature: String = self
+            .output_signatures
+            .iter()
+            .map(|(signature, _, _, _)| signature.as_str())
+            .collect();
+        let output_rust_type = self.get_output_rust_type();
+        let message = if self.output_signatures.is_empty() {
+            format!(
+                "`{}` has no return value, but `{}` returns `{{Self}}`",
+                name, function
+            )
+        } else {
+            format!(
+                "the return value of `{}` is `{}` ({}), but `{}` returns `{{Self}}`",
+                name,
+                output_signature,
+                rust_type_to_string(&output_rust_type),
+                function
+            )
+        };
+        checks.push(create_output_check(
+            function,
+            &output_rust_type,
+            &message,
+            span,
+        ));
+        checks
+    }
+
+    /// Create the call of the function with the arguments `arguments`. The arguments and the
+    /// return value are passed through the check functions.
+    fn create_call_code(&self, arguments: &[Ident]) -> TokenStream {
+        let span = self.name.span();
+        let function = &self.function;
+        let arguments = arguments.iter().enumerate().map(|(i, argument)| {
+            let check_function = get_argument_check_function(function, i, span);
+            quote_spanned! {span=> #check_function(#argument) }
+        });
+        let output = get_output_check_function(function, span);
+        quote_spanned! {span=>
+            #output(self.#function(&dbus, &header, #(#arguments),*).await)
+        }
+    }
+
     pub(super) fn create_code(&self) -> TokenStream {
         let name = &self.name;
-        let function = &self.function;
 
         let mut name_input_arguments = Vec::new();
         let mut parse_input_arguments = Vec::new();
-        for (i, (signature, rust_type, value_to_rust, _)) in
-            self.input_signatures.iter().enumerate()
+        for (i, ((signature, rust_type, value_to_rust, _), argument)) in self
+            .input_signatures
+            .iter()
+            .zip(&self.input_arguments)
+            .enumerate()
         {
-            let name = format_ident!("arg_{}", i.to_string());
+            let name = argument.get_ident(i, self.name.span());
             let parse_input_argument =
                 get_value_from_body_iter(&name, signature, rust_type, value_to_rust);
             parse_input_arguments.push(parse_input_argument);
@@ -61,7 +230,7 @@ impl Method {
             let (_, rust_type, _, rust_to_value) = &self.output_signatures[0];
             rust_type_output_arguments.push(quote! {
                 let i: #rust_type = result;
-                let v: dbus_message_parser::Value = #rust_to_value;
+                let v: __dbus_message_parser::Value = #rust_to_value;
                 msg.add_value(v);
             });
         } else {
@@ -69,7 +238,7 @@ impl Method {
                 let index = Index::from(i);
                 rust_type_output_arguments.push(quote! {
                     let i: #rust_type = result.#index;
-                    let v: dbus_message_parser::Value = #rust_to_value;
+                    let v: __dbus_message_parser::Value = #rust_to_value;
                     msg.add_value(v);
                 });
             }
@@ -79,74 +248,82 @@ impl Method {
         let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
         let check_result = check_result();
         let create_return_msg_from_header = create_return_msg_from_header();
+        let call = self.create_call_code(&name_input_arguments);
         quote! {
             #name => {
                 #check_input_signature
                 #(#parse_input_arguments)*
                 #check_if_no_value_from_body_iter;
-                let result = self.#function(&dbus, &header, #(#name_input_arguments),*).await;
+                let result = #call;
                 let result = #check_result;
                 let #mut_msg msg = #create_return_msg_from_header;
                 #(#rust_type_output_arguments)*
                 dbus.send(msg)?;
-                return Ok(());
+                return ::std::result::Result::Ok(());
             }
         }
     }
-}
-
-impl Introspectable for Method {
-    fn to_introspect(&self, xml: &mut String) {
-        *xml += &format!("    <method name=\"{}\">\n", self.name.value());
-        let mut i = 0;
 
-        for (signature, _, _, _) in &self.input_signatures {
-            let name = &format!("arg_{}", i);
-            *xml += &format!(
-                "      <arg type=\"{}\" name=\"{}\" direction=\"in\"/>\n",
-                signature, name,
-            );
-            i += 1;
-        }
-
-        for (signature, _, _, _) in &self.output_signatures {
-            let name = &format!("arg_{}", i);
-            *xml += &format!(
-                "      <arg type=\"{}\" name=\"{}\" direction=\"out\"/>\n",
-                signature, name,
-            );
-            i += 1;
+    /// Parse the named-key syntax:
+    /// `method(name = "Name", handler = function, args = "in", returns = "out")`.
+    fn try_from_named(meta_list: &MetaList) -> SynResult<Method> {
+        let mut name = None;
+        let mut function = None;
+        let mut input_signatures = Vec::new();
+        let mut input_arguments = Vec::new();
+        let mut output_signatures = Vec::new();
+        let mut output_arguments = Vec::new();
+        for (key, lit) in get_named_arguments(meta_list, &["name", "handler", "args", "returns"])? {
+            match key.to_string().as_ref() {
+                "name" => name = Some(get_lit_str_from_lit(lit)?),
+                "handler" => function = Some(get_ident_from_lit(lit)?),
+                "args" => {
+                    let arguments = get_arguments_from_lit_str(&get_lit_str_from_lit(lit)?)?;
+                    input_signatures = arguments.0;
+                    input_arguments = arguments.1;
+                }
+                "returns" => {
+                    let arguments = get_arguments_from_lit_str(&get_lit_str_from_lit(lit)?)?;
+                    output_signatures = arguments.0;
+                    output_arguments = arguments.1;
+                }
+                _ => unreachable!(),
+            }
         }
 
-        *xml += "    </method>\n";
+        let name = get_required_argument(meta_list, "name", name)?;
+        check_member_name(&name, "method")?;
+        let function = get_required_argument(meta_list, "handler", function)?;
+        Method::new(
+            name,
+            function,
+            input_signatures,
+            input_arguments,
+            output_signatures,
+            output_arguments,
+        )
     }
-}
-
-impl TryFrom<&MetaList> for Method {
-    type Error = SynError;
-
-    fn try_from(meta_list: &MetaList) -> Result<Self, Self::Error> {
-        // Get the ident and check if it is equal "method"
-        let meta_list_type = get_ident_from_path(&meta_list.path)?;
-        if meta_list_type != "method" {
-            return Err(SynError::new(meta_list_type.span(), "excepted \"method\""));
-        }
 
+    /// Parse the positional syntax: `method("Name", function, "in", "out")`.
+    /// The arguments can also be defined by `args(arg("name", "s"), ...)` and
+    /// `returns(arg("name", "s"), ...)`.
+    fn try_from_positional(meta_list: &MetaList) -> SynResult<Method> {
         let nested_iter = &mut meta_list.nested.iter();
 
         // Get the name of the method
         let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        check_member_name(&name, "method")?;
 
         // Get the name of the method to call
         let function = get_ident_from_option_nested_meta(nested_iter.next())?;
 
-        let mut input_signature = Vec::new();
-        let mut output_signature = Vec::new();
+        let mut input = (Vec::new(), Vec::new());
+        let mut output = (Vec::new(), Vec::new());
         if let Some(nested_meta) = nested_iter.next() {
-            get_signatures_from_nested_meta(nested_meta, &mut input_signature)?;
+            input = get_arguments_from_nested_meta(nested_meta, "args")?;
 
             if let Some(nested_meta) = nested_iter.next() {
-                get_signatures_from_nested_meta(nested_meta, &mut output_signature)?;
+                output = get_arguments_from_nested_meta(nested_meta, "returns")?;
 
                 if nested_iter.next().is_some() {
                     return Err(SynError::new(meta_list.span(), "too many arguments"));
@@ -154,11 +331,56 @@ impl TryFrom<&MetaList> for Method {
             }
         }
 
-        Ok(Method {
-            name,
-            function,
-            input_signatures: input_signature,
-            output_signatures: output_signature,
-        })
+        Method::new(name, function, input.0, input.1, output.0, output.1)
+    }
+}
+
+impl Introspectable for Method {
+    fn to_introspect(&self, xml: &mut String) {
+        doc_to_introspect(self.doc.as_ref(), xml, "    ");
+        *xml += &format!("    <method name=\"{}\">\n", self.name.value());
+
+        let input = self
+            .input_signatures
+            .iter()
+            .zip(&self.input_arguments)
+            .map(|argument| (argument, "in"));
+        let output = self
+            .output_signatures
+            .iter()
+            .zip(&self.output_arguments)
+            .map(|argument| (argument, "out"));
+        // The index of the default names is counted across the input and output arguments
+        for (i, (((signature, _, _, _), argument), direction)) in input.chain(output).enumerate() {
+            let name = argument.get_name(i);
+            argument.to_introspect(xml, signature, Some(&name), Some(direction));
+        }
+
+        annotations_to_introspect(&self.annotations, xml, "      ");
+
+        *xml += "    </method>\n";
+    }
+}
+
+impl TryFrom<&MetaList> for Method {
+    type Error = SynError;
+
+    fn try_from(meta_list: &MetaList) -> Result<Self, Self::Error> {
+        // Get the ident and check if it is equal "method"
+        let meta_list_type = get_ident_from_path(&meta_list.path)?;
+        if meta_list_type != "method" {
+            return Err(SynError::new(meta_list_type.span(), "excepted \"method\""));
+        }
+
+        let (meta_list, annotations) = split_annotations(meta_list)?;
+        let (meta_list, doc) = split_doc(&meta_list)?;
+        let mut method = if is_named_syntax(&meta_list) {
+            Method::try_from_named(&meta_list)?
+        } else {
+            Method::try_from_positional(&meta_list)?
+        };
+        method.annotations = annotations;
+        method.doc = doc;
+        Ok(method)
     }
 }
@@ -0,0 +1,325 @@
+use syn::{Error as SynError, LitStr, Result as SynResult};
+
+/// The name of the standard interface `Introspectable`, which is generated by default.
+pub(super) const INTROSPECTABLE_INTERFACE: &str = "org.freedesktop.DBus.Introspectable";
+
+/// The name of the standard interface `Properties`, which is generated if there are properties.
+pub(super) const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// The name of the standard interface `Peer`, which is generated by default.
+pub(super) const PEER_INTERFACE: &str = "org.freedesktop.DBus.Peer";
+
+/// The name of the standard interface `ObjectManager`, which is generated if a field has the
+/// `#[object_manager]` attribute.
+pub(super) const OBJECT_MANAGER_INTERFACE: &str = "org.freedesktop.DBus.ObjectManager";
+
+/// The standard interfaces, which are implemented by the generated code.
+pub(super) const STANDARD_INTERFACES: [&str; 4] = [
+    INTROSPECTABLE_INTERFACE,
+    PROPERTIES_INTERFACE,
+    PEER_INTERFACE,
+    OBJECT_MANAGER_INTERFACE,
+];
+
+/// The maximum length of an interface or a member name.
+const MAXIMUM_NAME_LENGTH: usize = 255;
+
+/// Check if the string is a valid element of a name: `[A-Za-z_][A-Za-z0-9_]*`.
+fn check_element(element: &str) -> Result<(), String> {
+    let mut chars = element.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        Some(c) if c.is_ascii_digit() => return Err("must not begin with a digit".to_string()),
+        Some(c) => return Err(format!("contains the invalid character {:?}", c)),
+        None => return Err("must not be empty".to_string()),
+    }
+    for c in chars {
+        if !(c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("contains the invalid character {:?}", c));
+        }
+    }
+    Ok(())
+}
+
+fn check_length(name: &LitStr, kind: &str, value: &str) -> SynResult<()> {
+    if value.len() > MAXIMUM_NAME_LENGTH {
+        Err(SynError::new(
+            name.span(),
+            format!(
+                "invalid {} name: it is longer than {} characters",
+                kind, MAXIMUM_NAME_LENGTH
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Check if the literal is a valid dotted name, which is used for interfaces and annotations.
+fn check_dotted_name(name: &LitStr, kind: &str) -> SynResult<()> {
+    let value = name.value();
+    check_length(name, kind, &value)?;
+    let elements: Vec<&str> = value.split('.').collect();
+    if elements.len() < 2 {
+        return Err(SynError::new(
+            name.span(),
+            format!(
+                "invalid {} name {:?}: it must have at least two elements separated by a dot",
+                kind, value
+            ),
+        ));
+    }
+    for (i, element) in elements.iter().enumerate() {
+        if let Err(reason) = check_element(element) {
+            return Err(SynError::new(
+                name.span(),
+                format!(
+                    "invalid {} name {:?}: element {} {}",
+                    kind,
+                    value,
+                    i + 1,
+                    reason
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check if the literal is a valid [interface name].
+///
+/// [interface name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-interface
+pub(super) fn check_interface_name(name: &LitStr) -> SynResult<()> {
+    check_dotted_name(name, "interface")
+}
+
+/// Check if the literal is a valid annotation name. An annotation name has the same format as an
+/// interface name (e.g. `org.freedesktop.DBus.Deprecated`).
+pub(super) fn check_annotation_name(name: &LitStr) -> SynResult<()> {
+    check_dotted_name(name, "annotation")
+}
+
+/// Check if the interface collides with a standard interface, which is generated (`generated`).
+/// A generated standard interface can only be declared, if it replaces the generated
+/// implementation. If the generated implementation is disabled, then it can be declared without
+/// `replace`. Only standard interfaces can be replaced.
+pub(super) fn check_standard_interface(
+    name: &LitStr,
+    replace: bool,
+    generated: &[&str],
+) -> SynResult<()> {
+    let value = name.value();
+    let is_standard = STANDARD_INTERFACES.contains(&value.as_str());
+    if generated.contains(&value.as_str()) && !replace {
+        Err(SynError::new(
+            name.span(),
+            format!(
+                "{} collides with the generated implementation, add `replace` to replace it",
+                value
+            ),
+        ))
+    } else if !is_standard && replace {
+        Err(SynError::new(
+            name.span(),
+            format!(
+                "{} is not a standard interface, only standard interfaces can be replaced",
+                value
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Check if the literal is a valid [member name]. This is used for methods, signals and
+/// properties.
+///
+/// [member name]: https://dbus.freedesktop.org/doc/dbus-specification.html#message-protocol-names-member
+pub(super) fn check_member_name(name: &LitStr, kind: &str) -> SynResult<()> {
+    let value = name.value();
+    check_length(name, kind, &value)?;
+    if let Err(reason) = check_element(&value) {
+        Err(SynError::new(
+            name.span(),
+            format!("invalid {} name {:?}: it {}", kind, value, reason),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Check if a name is defined multiple times.
+/// The error points at the second definition.
+pub(super) fn check_duplicates<'a, I>(names: I, kind: &str) -> SynResult<()>
+where
+    I: IntoIterator<Item = &'a LitStr>,
+{
+    let mut values: Vec<String> = Vec::new();
+    for name in names {
+        let value = name.value();
+        if values.contains(&value) {
+            return Err(SynError::new(
+                name.span(),
+                format!("{} {:?} is defined multiple times", kind, value),
+            ));
+        }
+        values.push(value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_annotation_name, check_dotted_name, check_duplicates, check_interface_name,
+        check_member_name, check_standard_interface, PEER_INTERFACE, PROPERTIES_INTERFACE,
+    };
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    fn lit_str(value: &str) -> LitStr {
+        LitStr::new(value, Span::call_site())
+    }
+
+    fn interface_error(value: &str) -> String {
+        check_interface_name(&lit_str(value))
+            .unwrap_err()
+            .to_string()
+    }
+
+    fn member_error(value: &str) -> String {
+        check_member_name(&lit_str(value), "method")
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn valid_interface_names() {
+        for value in &["org.example", "org.example.interface", "_a.b_1", "A1.B2.C3"] {
+            assert!(check_interface_name(&lit_str(value)).is_ok(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn invalid_interface_names() {
+        assert_eq!(
+            interface_error("org"),
+            "invalid interface name \"org\": it must have at least two elements separated by a dot"
+        );
+        assert_eq!(
+            interface_error("org..x"),
+            "invalid interface name \"org..x\": element 2 must not be empty"
+        );
+        assert_eq!(
+            interface_error("1abc.x"),
+            "invalid interface name \"1abc.x\": element 1 must not begin with a digit"
+        );
+        assert_eq!(
+            interface_error("org.ex-ample"),
+            "invalid interface name \"org.ex-ample\": element 2 contains the invalid character '-'"
+        );
+        assert_eq!(
+            interface_error("org.example."),
+            "invalid interface name \"org.example.\": element 3 must not be empty"
+        );
+    }
+
+    #[test]
+    fn too_long_names() {
+        let value = format!("org.{}", "a".repeat(251));
+        assert!(check_interface_name(&lit_str(&value)).is_ok());
+        let value = format!("org.{}", "a".repeat(252));
+        assert_eq!(
+            interface_error(&value),
+            "invalid interface name: it is longer than 255 characters"
+        );
+        assert_eq!(
+            member_error(&"a".repeat(256)),
+            "invalid method name: it is longer than 255 characters"
+        );
+    }
+
+    #[test]
+    fn dotted_name_kind() {
+        let error = check_dotted_name(&lit_str("Deprecated"), "annotation").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid annotation name \"Deprecated\": it must have at least two elements separated \
+            by a dot"
+        );
+    }
+
+    #[test]
+    fn member_names() {
+        for value in &["Method", "method_1", "_Method"] {
+            assert!(
+                check_member_name(&lit_str(value), "method").is_ok(),
+                "{}",
+                value
+            );
+        }
+        assert_eq!(
+            member_error("Get.All"),
+            "invalid method name \"Get.All\": it contains the invalid character '.'"
+        );
+        assert_eq!(
+            member_error("1Method"),
+            "invalid method name \"1Method\": it must not begin with a digit"
+        );
+        assert_eq!(
+            member_error(""),
+            "invalid method name \"\": it must not be empty"
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let names = [lit_str("A"), lit_str("B"), lit_str("C")];
+        assert!(check_duplicates(&names, "method").is_ok());
+        let names = [lit_str("A"), lit_str("B"), lit_str("A")];
+        assert_eq!(
+            check_duplicates(&names, "method").unwrap_err().to_string(),
+            "method \"A\" is defined multiple times"
+        );
+    }
+
+    #[test]
+    fn standard_interfaces() {
+        let properties = lit_str(PROPERTIES_INTERFACE);
+        let generated = [PROPERTIES_INTERFACE];
+        assert_eq!(
+            check_standard_interface(&properties, false, &generated)
+                .unwrap_err()
+                .to_string(),
+            "org.freedesktop.DBus.Properties collides with the generated implementation, add \
+            `replace` to replace it"
+        );
+        assert!(check_standard_interface(&properties, true, &generated).is_ok());
+        // A disabled standard interface can be declared without `replace`
+        assert!(check_standard_interface(&lit_str(PEER_INTERFACE), false, &generated).is_ok());
+        assert!(check_standard_interface(&lit_str(PEER_INTERFACE), true, &generated).is_ok());
+
+        let interface = lit_str("org.example.interface");
+        assert!(check_standard_interface(&interface, false, &generated).is_ok());
+        assert_eq!(
+            check_standard_interface(&interface, true, &generated)
+                .unwrap_err()
+                .to_string(),
+            "org.example.interface is not a standard interface, only standard interfaces can be \
+            replaced"
+        );
+    }
+
+    #[test]
+    fn annotation_names() {
+        let name = lit_str("org.freedesktop.DBus.Deprecated");
+        assert!(check_annotation_name(&name).is_ok());
+        assert_eq!(
+            check_annotation_name(&lit_str("org.freedesktop.DBus.Deprecated!"))
+                .unwrap_err()
+                .to_string(),
+            "invalid annotation name \"org.freedesktop.DBus.Deprecated!\": element 4 contains the \
+            invalid character '!'"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::code::{get_interface_from_header, unknown_interface_from_header};
+use crate::crate_path::create_crate_aliases_code;
+use crate::delegate::{create_delegate_functions_code, create_delegates_code, Delegate};
+use crate::fallback::create_fallback_code;
+use crate::interface::Interface;
+use crate::introspectable::{
+    create_introspectable_code, create_introspection_xml_code, Introspectable,
+};
+use crate::name::{
+    INTROSPECTABLE_INTERFACE, OBJECT_MANAGER_INTERFACE, PEER_INTERFACE, PROPERTIES_INTERFACE,
+};
+use crate::object_manager::{create_managed_functions_code, ObjectManager};
+use crate::peer::create_peer_code;
+use crate::properties::create_properties_code;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{parse_quote, GenericParam, Generics, Path, Result as SynResult, WherePredicate};
+
+/// Add the bounds, which are needed by `dbus_async::Handler`, to the generic parameters.
+fn add_handler_bounds(generics: &mut Generics) {
+    let mut predicates: Vec<WherePredicate> = Vec::new();
+    for param in &generics.params {
+        match param {
+            GenericParam::Type(type_param) => {
+                let ident = &type_param.ident;
+                predicates.push(
+                    parse_quote!(#ident: ::std::marker::Send + ::std::marker::Sync + 'static),
+                );
+            }
+            GenericParam::Lifetime(lifetime_def) => {
+                let lifetime = &lifetime_def.lifetime;
+                predicates.push(parse_quote!(#lifetime: 'static));
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    generics.make_where_clause().predicates.extend(predicates);
+}
+
+/// A DBus object, which has all the interfaces of a type.
+pub(crate) struct Object {
+    interfaces: Vec<Interface>,
+    introspectable: bool,
+    peer: bool,
+    fallback: Option<Ident>,
+    delegates: Vec<Delegate>,
+    object_manager: Option<ObjectManager>,
+    crate_path: Option<Path>,
+}
+
+impl Object {
+    pub(super) fn new(
+        interfaces: Vec<Interface>,
+        introspectable: bool,
+        peer: bool,
+        fallback: Option<Ident>,
+        delegates: Vec<Delegate>,
+        object_manager: Option<ObjectManager>,
+        crate_path: Option<Path>,
+    ) -> Object {
+        Object {
+            interfaces,
+            introspectable,
+            peer,
+            fallback,
+            delegates,
+            object_manager,
+            crate_path,
+        }
+    }
+
+    /// Check if an interface collides with a standard interface, which is generated. A standard
+    /// interface, which is disabled (e.g. `#[peer(false)]`), can be declared without `replace`.
+    pub(super) fn check_standard_interfaces(&self) -> SynResult<()> {
+        let mut generated = vec![PROPERTIES_INTERFACE];
+        if self.introspectable {
+            generated.push(INTROSPECTABLE_INTERFACE);
+        }
+        if self.peer {
+            generated.push(PEER_INTERFACE);
+        }
+        if self.object_manager.is_some() {
+            generated.push(OBJECT_MANAGER_INTERFACE);
+        }
+        for interface in &self.interfaces {
+            interface.check_standard_interface(&generated)?;
+        }
+        Ok(())
+    }
+
+    /// Check if an interface replaces the generated implementation of the standard interface
+    /// `name`.
+    fn is_replaced(&self, name: &str) -> bool {
+        self.interfaces
+            .iter()
+            .any(|interface| interface.is_replacing(name))
+    }
+
+    /// Create the hidden functions, which are needed to delegate to this type.
+    fn create_delegate_functions_code(&self, have_properties: bool) -> TokenStream {
+        let mut interface_names = Vec::new();
+        let mut interfaces_xml = String::new();
+        for interface in &self.interfaces {
+            interface_names.push(interface.get_name().value());
+            interface.to_introspect(&mut interfaces_xml);
+        }
+        create_delegate_functions_code(
+            &interface_names,
+            have_properties,
+            &interfaces_xml,
+            &self.delegates,
+        )
+    }
+
+    /// Create the items, which check the types of all bound functions and fields. The generated
+    /// code passes the values through the check functions, so a wrong type is reported at the
+    /// attribute. A function, which is bound multiple times, is only checked once.
+    fn create_check_code(&self) -> Vec<TokenStream> {
+        let mut check_functions = Vec::new();
+        let mut checks = Vec::new();
+        for interface in &self.interfaces {
+            for (check_function, items) in interface.create_check_code() {
+                if !check_functions.contains(&check_function) {
+                    check_functions.push(check_function);
+                    checks.push(items);
+                }
+            }
+        }
+        checks
+    }
+
+    /// Create the `dbus_async::Handler` implementation for the type `self_type`.
+    /// The code is placed in an anonymous constant, so the aliases of the crates do not leak into
+    /// the scope of the type.
+    pub(super) fn create_handler_code(
+        &self,
+        self_type: &TokenStream,
+        mut generics: Generics,
+    ) -> TokenStream {
+        add_handler_bounds(&mut generics);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let delegates_code = create_delegates_code(&self.delegates);
+
+        let mut interfaces_code = Vec::new();
+
+        let have_peer = self.peer && !self.is_replaced(PEER_INTERFACE);
+        let have_object_manager =
+            self.object_manager.is_some() && !self.is_replaced(OBJECT_MANAGER_INTERFACE);
+        let have_introspectable =
+            self.introspectable && !self.is_replaced(INTROSPECTABLE_INTERFACE);
+        let mut standard_interfaces = Vec::new();
+        if have_introspectable {
+            standard_interfaces.push(INTROSPECTABLE_INTERFACE);
+        }
+        if have_peer {
+            standard_interfaces.push(PEER_INTERFACE);
+        }
+        if have_object_manager {
+            standard_interfaces.push(OBJECT_MANAGER_INTERFACE);
+        }
+
+        let have_properties = !self.is_replaced(PROPERTIES_INTERFACE);
+        if have_properties {
+            let mut properties_standard_interfaces = standard_interfaces.clone();
+            properties_standard_interfaces.push(PROPERTIES_INTERFACE);
+            interfaces_code.push(create_properties_code(
+                &self.interfaces,
+                &properties_standard_interfaces,
+            ));
+        }
+
+        if have_peer {
+            interfaces_code.push(create_peer_code());
+        }
+
+        let mut object_manager_functions_code = None;
+        if let Some(object_manager) = &self.object_manager {
+            object_manager_functions_code = Some(object_manager.create_functions_code());
+            if have_object_manager {
+                interfaces_code.push(object_manager.create_code());
+            }
+        }
+
+        if have_introspectable {
+            interfaces_code.push(create_introspectable_code());
+        }
+        let introspection_xml_code = create_introspection_xml_code(
+            &self.interfaces,
+            have_introspectable,
+            have_properties,
+            have_peer,
+            have_object_manager,
+            &self.delegates,
+        );
+        let introspection_consts_code: Vec<TokenStream> = self
+            .interfaces
+            .iter()
+            .map(Interface::create_introspection_const_code)
+            .collect();
+
+        for interface in &self.interfaces {
+            if let Some(code) = interface.create_methods_code() {
+                interfaces_code.push(code);
+            }
+        }
+
+        let get_interface_from_header = get_interface_from_header();
+        let unknown_interface_from_header = if let Some(fallback) = &self.fallback {
+            create_fallback_code(fallback)
+        } else {
+            unknown_interface_from_header()
+        };
+        let delegate_functions_code = self.create_delegate_functions_code(have_properties);
+        let managed_functions_code =
+            create_managed_functions_code(&self.interfaces, &standard_interfaces, &self.delegates);
+        let mut emit_functions_code = Vec::new();
+        for interface in &self.interfaces {
+            emit_functions_code.extend(interface.create_emit_functions_code());
+        }
+        let check_code = self.create_check_code();
+        let uses_log = self.interfaces.iter().any(Interface::uses_log);
+        // The object manager needs the channel of `futures` to bind the managed objects
+        let uses_futures = self.object_manager.is_some();
+        let crate_aliases_code =
+            create_crate_aliases_code(self.crate_path.as_ref(), uses_log, uses_futures);
+        // The functions can be deprecated, because of the `org.freedesktop.DBus.Deprecated`
+        // annotation, but they are still called by the generated code
+        quote! {
+            #[allow(deprecated)]
+            const _: () = {
+                #crate_aliases_code
+
+                #(#check_code)*
+
+                impl #impl_generics #self_type #where_clause {
+                    #delegate_functions_code
+
+                    #managed_functions_code
+
+                    #object_manager_functions_code
+
+                    #(#emit_functions_code)*
+
+                    #(#introspection_consts_code)*
+
+                    #introspection_xml_code
+                }
+
+                #[__async_trait::async_trait]
+                impl #impl_generics __dbus_async::Handler for #self_type #where_clause {
+                    async fn handle(&mut self, dbus: &__dbus_async::DBus, msg: __dbus_message_parser::Message) -> __dbus_async::DBusResult<()> {
+                        if msg.get_type() != __dbus_message_parser::MessageType::MethodCall {
+                            return ::std::result::Result::Ok(())
+                        }
+                        let (header, body) = msg.split();
+                        #delegates_code
+                        let mut body_iter = body.into_iter();
+                        match #get_interface_from_header {
+                            #(#interfaces_code)*
+                            _ => #unknown_interface_from_header
+                        }
+                    }
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use crate::code::{
+    check_if_no_signature_from_header, check_if_no_value_from_body_iter, check_result,
+    create_return_msg_from_header, get_member_from_header, unknown_member_from_header,
+};
+use crate::delegate::Delegate;
+use crate::interface::Interface;
+use crate::name::OBJECT_MANAGER_INTERFACE;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    Data, DeriveInput, Error as SynError, Fields, GenericArgument, Member, Meta, PathArguments,
+    Result as SynResult, Type,
+};
+
+pub(super) static OBJECT_MANAGER_XML: &str = r#"
+   <interface name="org.freedesktop.DBus.ObjectManager">
+     <method name="GetManagedObjects">
+       <arg type="a{oa{sa{sv}}}" name="object_paths_interfaces_and_properties" direction="out"/>
+     </method>
+     <signal name="InterfacesAdded">
+       <arg type="o" name="object_path"/>
+       <arg type="a{sa{sv}}" name="interfaces_and_properties"/>
+     </signal>
+     <signal name="InterfacesRemoved">
+       <arg type="o" name="object_path"/>
+       <arg type="as" name="interfaces"/>
+     </signal>
+   </interface>
+"#;
+
+/// A field, which contains the managed objects of the `org.freedesktop.DBus.ObjectManager`
+/// interface.
+/// The type of the field has to be a map from `ObjectPath` to `Arc<Mutex<T>>`, where `T`
+/// implements `dbus_async::Handler` with this crate.
+pub(crate) struct ObjectManager {
+    member: Member,
+    object_type: Type,
+}
+
+/// Get the type of the values of a map type, for example `V` of `BTreeMap<K, V>`.
+fn get_value_type(field_type: &Type) -> SynResult<Type> {
+    if let Type::Path(type_path) = field_type {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                let mut types = arguments.args.iter().filter_map(|argument| match argument {
+                    GenericArgument::Type(argument_type) => Some(argument_type),
+                    _ => None,
+                });
+                if let (Some(_), Some(value_type)) = (types.next(), types.next()) {
+                    return Ok(value_type.clone());
+                }
+            }
+        }
+    }
+    Err(SynError::new(
+        field_type.span(),
+        "excepted a map from `ObjectPath` to the managed objects, for example \
+        `BTreeMap<ObjectPath, Arc<Mutex<T>>>`",
+    ))
+}
+
+/// Get the field, which has the `#[object_manager]` attribute.
+pub(super) fn parse_object_manager(ast: &DeriveInput) -> SynResult<Option<ObjectManager>> {
+    let fields = match &ast.data {
+        Data::Struct(data_struct) => &data_struct.fields,
+        _ => return Ok(None),
+    };
+
+    let fields: Vec<_> = match fields {
+        Fields::Named(fields_named) => fields_named.named.iter().collect(),
+        Fields::Unnamed(fields_unnamed) => fields_unnamed.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let mut object_manager = None;
+    for (i, field) in fields.into_iter().enumerate() {
+        for attribute in &field.attrs {
+            if !attribute.path.is_ident("object_manager") {
+                continue;
+            }
+            if !matches!(attribute.parse_meta()?, Meta::Path(_)) {
+                return Err(SynError::new(
+                    attribute.span(),
+                    "excepted \"object_manager\"",
+                ));
+            }
+            if object_manager.is_some() {
+                return Err(SynError::new(
+                    attribute.span(),
+                    "Object manager is defined multiple times",
+                ));
+            }
+
+            let member = if let Some(ident) = &field.ident {
+                Member::Named(ident.clone())
+            } else {
+                Member::Unnamed(i.into())
+            };
+            object_manager = Some(ObjectManager {
+                member,
+                object_type: get_value_type(&field.ty)?,
+            });
+        }
+    }
+    Ok(object_manager)
+}
+
+/// Create the code, which converts a `DBusError` into an error of the handler functions.
+fn create_map_dbus_error_code() -> TokenStream {
+    quote! {
+        |e| (
+            ::std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.Failed".to_string()).unwrap(),
+            e.to_string(),
+        )
+    }
+}
+
+impl ObjectManager {
+    /// Create the code of the `org.freedesktop.DBus.ObjectManager` interface.
+    pub(super) fn create_code(&self) -> TokenStream {
+        let member = &self.member;
+        let check_if_no_signature_from_header = check_if_no_signature_from_header();
+        let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
+        let get_member_from_header = get_member_from_header();
+        let check_result = check_result();
+        let create_return_msg_from_header = create_return_msg_from_header();
+        let unknown_member_from_header = unknown_member_from_header();
+        quote! {
+            "org.freedesktop.DBus.ObjectManager" => {
+                match #get_member_from_header {
+                    "GetManagedObjects" => {
+                        #check_if_no_signature_from_header;
+                        #check_if_no_value_from_body_iter;
+                        let mut objects = ::std::vec::Vec::new();
+                        for (object_path, object) in self.#member.iter() {
+                            let result = object.lock().await.__dbus_get_managed_interfaces(dbus, &header).await;
+                            let interfaces = #check_result;
+                            let object_path = __dbus_message_parser::Value::ObjectPath(object_path.clone());
+                            objects.push(__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((object_path, interfaces))));
+                        }
+                        let mut msg = #create_return_msg_from_header;
+                        msg.add_value(__dbus_message_parser::Value::Array(objects, "{oa{sa{sv}}}".to_string()));
+                        return dbus.send(msg);
+                    }
+                    _ => #unknown_member_from_header
+                }
+            }
+        }
+    }
+
+    /// Create the functions, which add and remove the managed objects.
+    pub(super) fn create_functions_code(&self) -> TokenStream {
+        let member = &self.member;
+        let object_type = &self.object_type;
+        let map_dbus_error = create_map_dbus_error_code();
+        quote! {
+            /// Bind `object` to `object_path`, add it to the managed objects and emit the
+            /// `InterfacesAdded` signal from `manager_path`.
+            /// The object path is registered before the signal is emitted, so the object can be
+            /// called by the receivers of the signal. The returned future handles the messages of
+            /// the object and has to be spawned.
+            /// If `object_path` is not below `manager_path`, then a
+            /// `org.freedesktop.DBus.Error.InvalidArgs` error is returned and if `object_path` is
+            /// already managed, then a `org.freedesktop.DBus.Error.ObjectPathInUse` error is
+            /// returned. If the signal cannot be sent, then the object is unbound again.
+            /// The getters of the properties get the header of the signal.
+            pub async fn add_managed_object(
+                &mut self,
+                dbus: &__dbus_async::DBus,
+                manager_path: &__dbus_message_parser::ObjectPath,
+                object_path: __dbus_message_parser::ObjectPath,
+                object: #object_type,
+            ) -> ::std::result::Result<
+                ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = __dbus_async::DBusResult<()>> + ::std::marker::Send>>,
+                (__dbus_message_parser::Error, ::std::string::String),
+            > {
+                if !object_path.starts_with(manager_path) {
+                    return ::std::result::Result::Err((
+                        ::std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.InvalidArgs".to_string()).unwrap(),
+                        ::std::format!("The object path `{}` is not below `{}`", object_path, manager_path),
+                    ));
+                }
+                if self.#member.contains_key(&object_path) {
+                    return ::std::result::Result::Err((
+                        ::std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.ObjectPathInUse".to_string()).unwrap(),
+                        ::std::format!("The object path `{}` is already managed", object_path),
+                    ));
+                }
+
+                let msg = __dbus_message_parser::Message::signal(
+                    manager_path.clone(),
+                    ::std::convert::TryFrom::try_from(#OBJECT_MANAGER_INTERFACE).unwrap(),
+                    ::std::convert::TryFrom::try_from("InterfacesAdded").unwrap(),
+                );
+                let (header, body) = msg.split();
+                let interfaces = object.lock().await.__dbus_get_managed_interfaces(dbus, &header).await?;
+                let mut msg = __dbus_message_parser::Message::new(header, body);
+                msg.add_value(__dbus_message_parser::Value::ObjectPath(object_path.clone()));
+                msg.add_value(interfaces);
+
+                let (sender, receiver) = __futures::channel::mpsc::channel(128);
+                dbus.add_method_call(object_path.clone(), sender).map_err(#map_dbus_error)?;
+                self.#member.insert(object_path.clone(), ::std::clone::Clone::clone(&object));
+                if let ::std::result::Result::Err(e) = dbus.send(msg) {
+                    self.#member.remove(&object_path);
+                    let _ = dbus.delete_object_path(object_path);
+                    return ::std::result::Result::Err(e).map_err(#map_dbus_error);
+                }
+                ::std::result::Result::Ok(__dbus_async::Binder::bind_by_receiver(
+                    object,
+                    dbus.clone(),
+                    receiver,
+                ))
+            }
+
+            /// Unbind the object at `object_path`, remove it from the managed objects and emit the
+            /// `InterfacesRemoved` signal from `manager_path`.
+            /// It returns the object, if it was managed. The object is only removed from the
+            /// managed objects, if it could be unbound.
+            pub async fn remove_managed_object(
+                &mut self,
+                dbus: &__dbus_async::DBus,
+                manager_path: &__dbus_message_parser::ObjectPath,
+                object_path: &__dbus_message_parser::ObjectPath,
+            ) -> ::std::result::Result<
+                ::std::option::Option<#object_type>,
+                (__dbus_message_parser::Error, ::std::string::String),
+            > {
+                if !self.#member.contains_key(object_path) {
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+                dbus.delete_object_path(object_path.clone()).map_err(#map_dbus_error)?;
+                let object = self.#member.remove(object_path).unwrap();
+
+                let interfaces = object
+                    .lock()
+                    .await
+                    .__dbus_get_managed_interface_names()
+                    .into_iter()
+                    .map(__dbus_message_parser::Value::String)
+                    .collect();
+                let mut msg = __dbus_message_parser::Message::signal(
+                    manager_path.clone(),
+                    ::std::convert::TryFrom::try_from(#OBJECT_MANAGER_INTERFACE).unwrap(),
+                    ::std::convert::TryFrom::try_from("InterfacesRemoved").unwrap(),
+                );
+                msg.add_value(__dbus_message_parser::Value::ObjectPath(object_path.clone()));
+                msg.add_value(__dbus_message_parser::Value::Array(interfaces, "s".to_string()));
+                dbus.send(msg).map_err(#map_dbus_error)?;
+                ::std::result::Result::Ok(::std::option::Option::Some(object))
+            }
+        }
+    }
+}
+
+/// Create the hidden functions, which get the interfaces and the properties of an object, if it
+/// is managed by an object manager.
+/// `standard_interfaces` are the generated standard interfaces except of
+/// `org.freedesktop.DBus.Properties`, which is added, if the object has properties.
+pub(super) fn create_managed_functions_code(
+    interfaces: &[Interface],
+    standard_interfaces: &[&str],
+    delegates: &[Delegate],
+) -> TokenStream {
+    let mut interface_names = Vec::new();
+    let mut interfaces_and_properties = Vec::new();
+    for interface in interfaces {
+        interface_names.push(interface.get_name().value());
+        interfaces_and_properties.push(interface.create_properties_dict_code());
+    }
+    let mut delegates_interface_names = Vec::new();
+    let mut delegates_interfaces_and_properties = Vec::new();
+    for delegate in delegates {
+        delegates_interface_names.push(delegate.create_interface_names_code());
+        delegates_interfaces_and_properties.push(delegate.create_interfaces_and_properties_code());
+    }
+
+    quote! {
+        #[doc(hidden)]
+        pub async fn __dbus_get_interfaces_and_properties(
+            &mut self,
+            dbus: &__dbus_async::DBus,
+            header: &__dbus_message_parser::MessageHeader,
+            interfaces: &mut ::std::vec::Vec<__dbus_message_parser::Value>,
+        ) -> ::std::result::Result<(), (__dbus_message_parser::Error, ::std::string::String)> {
+            #(
+                let properties = #interfaces_and_properties;
+                let interface = __dbus_message_parser::Value::String(#interface_names.to_string());
+                interfaces.push(__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((interface, properties))));
+            )*
+            #(#delegates_interfaces_and_properties)*
+            ::std::result::Result::Ok(())
+        }
+
+        #[doc(hidden)]
+        pub fn __dbus_get_interface_names(&self, names: &mut ::std::vec::Vec<::std::string::String>) {
+            #(names.push(#interface_names.to_string());)*
+            #(#delegates_interface_names)*
+        }
+
+        #[doc(hidden)]
+        pub async fn __dbus_get_managed_interfaces(
+            &mut self,
+            dbus: &__dbus_async::DBus,
+            header: &__dbus_message_parser::MessageHeader,
+        ) -> ::std::result::Result<__dbus_message_parser::Value, (__dbus_message_parser::Error, ::std::string::String)> {
+            let mut interfaces = ::std::vec::Vec::new();
+            for name in self.__dbus_get_standard_interface_names() {
+                let interface = __dbus_message_parser::Value::String(name);
+                let properties = __dbus_message_parser::Value::Array(::std::vec::Vec::new(), "{sv}".to_string());
+                interfaces.push(__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((interface, properties))));
+            }
+            self.__dbus_get_interfaces_and_properties(dbus, header, &mut interfaces).await?;
+            ::std::result::Result::Ok(__dbus_message_parser::Value::Array(interfaces, "{sa{sv}}".to_string()))
+        }
+
+        #[doc(hidden)]
+        pub fn __dbus_get_managed_interface_names(&self) -> ::std::vec::Vec<::std::string::String> {
+            let mut names = self.__dbus_get_standard_interface_names();
+            self.__dbus_get_interface_names(&mut names);
+            names
+        }
+
+        fn __dbus_get_standard_interface_names(&self) -> ::std::vec::Vec<::std::string::String> {
+            let mut names: ::std::vec::Vec<::std::string::String> =
+                ::std::vec![#(#standard_interfaces.to_string()),*];
+            if Self::__dbus_has_properties() {
+                names.push("org.freedesktop.DBus.Properties".to_string());
+            }
+            names
+        }
+    }
+}
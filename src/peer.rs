@@ -0,0 +1,103 @@
+use crate::code::{
+    check_if_no_signature_from_header, check_if_no_value_from_body_iter, create_error_code,
+    create_return_msg_from_header, get_member_from_header, unknown_member_from_header,
+};
+use crate::helper::{get_ident_from_path, get_lit_bool_from_option_nested_meta};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Error as SynError, MetaList, Result as SynResult};
+
+pub(super) static PEER_XML: &str = r#"
+   <interface name="org.freedesktop.DBus.Peer">
+     <method name="Ping"/>
+     <method name="GetMachineId">
+       <arg type="s" name="machine_uuid" direction="out"/>
+     </method>
+   </interface>
+"#;
+
+/// The files, which can contain the machine ID. The first file, which can be read, is used.
+const MACHINE_ID_FILES: [&str; 2] = ["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+/// Create the code, which reads the machine ID from the machine ID files.
+/// The machine ID is only read once and then cached per handler type. If no file can be read, then
+/// a `org.freedesktop.DBus.Error.Failed` error is returned and the files are read again by the next
+/// call.
+fn create_read_machine_id_code() -> TokenStream {
+    let [first_file, second_file] = MACHINE_ID_FILES;
+    let error = create_error_code(
+        "Failed",
+        quote! {
+            ::std::format!(
+                "Could not read the machine ID from {} or {}: {}",
+                #first_file,
+                #second_file,
+                e
+            )
+        },
+    );
+    quote! {
+        {
+            static MACHINE_ID: ::std::sync::OnceLock<::std::string::String> = ::std::sync::OnceLock::new();
+            match MACHINE_ID.get() {
+                ::std::option::Option::Some(machine_id) => machine_id.clone(),
+                ::std::option::Option::None => {
+                    match ::std::fs::read_to_string(#first_file)
+                        .or_else(|_| ::std::fs::read_to_string(#second_file))
+                    {
+                        ::std::result::Result::Ok(machine_id) => {
+                            MACHINE_ID.get_or_init(|| machine_id.trim().to_string()).clone()
+                        }
+                        ::std::result::Result::Err(e) => #error,
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn create_peer_code() -> TokenStream {
+    let check_if_no_signature_from_header = check_if_no_signature_from_header();
+    let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
+    let get_member_from_header = get_member_from_header();
+    let create_return_msg_from_header = create_return_msg_from_header();
+    let unknown_member_from_header = unknown_member_from_header();
+    let read_machine_id = create_read_machine_id_code();
+    quote! {
+        "org.freedesktop.DBus.Peer" => {
+            match #get_member_from_header {
+                "Ping" => {
+                    #check_if_no_signature_from_header;
+                    #check_if_no_value_from_body_iter;
+                    let msg = #create_return_msg_from_header;
+                    return dbus.send(msg);
+                }
+                "GetMachineId" => {
+                    #check_if_no_signature_from_header;
+                    #check_if_no_value_from_body_iter;
+                    let machine_id = #read_machine_id;
+                    let mut msg = #create_return_msg_from_header;
+                    msg.add_value(__dbus_message_parser::Value::String(machine_id));
+                    return dbus.send(msg);
+                }
+                _ => #unknown_member_from_header
+            }
+        }
+    }
+}
+
+pub(super) fn parse_peer(meta_list: &MetaList) -> SynResult<bool> {
+    let meta_list_type = get_ident_from_path(&meta_list.path)?;
+    if meta_list_type != "peer" {
+        return Err(SynError::new(meta_list_type.span(), "excepted \"peer\""));
+    }
+    let nested_iter = &mut meta_list.nested.iter();
+
+    let lit_bool = get_lit_bool_from_option_nested_meta(nested_iter.next())?;
+
+    if nested_iter.next().is_some() {
+        return Err(SynError::new(meta_list.span(), "too many arguments"));
+    }
+    Ok(lit_bool.value)
+}
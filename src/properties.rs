@@ -1,140 +1,196 @@
 use crate::code::{
     check_if_no_value_from_body_iter, check_signature_from_header, create_return_msg_from_header,
     get_member_from_header, get_string_from_body_iter, get_variant_from_body_iter,
-    unknown_interface_from_header, unknown_member_from_header,
+    unknown_interface_from_body, unknown_member_from_header, unknown_property_from_header,
 };
+use crate::helper::{did_you_mean, get_lit_str_from_lit};
 use crate::interface::Interface;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use std::convert::TryFrom;
+use syn::{Error as SynError, Lit, LitStr};
 
-fn create_get_code(interfaces: &[Interface]) -> Option<TokenStream> {
-    let mut properties = Vec::new();
-    for interface in interfaces {
-        if let Some(code) = interface.create_get_code() {
-            properties.push(code);
+/// What `GetAll` does, if a get function of a property fails.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum GetAllPolicy {
+    /// The whole call fails with the error of the get function.
+    Fail,
+    /// The property is skipped.
+    Skip,
+    /// The property is skipped and the error is logged by the `log` crate.
+    Log,
+}
+
+impl TryFrom<&Lit> for GetAllPolicy {
+    type Error = SynError;
+
+    fn try_from(lit: &Lit) -> Result<Self, Self::Error> {
+        let value = get_lit_str_from_lit(lit)?;
+        match value.value().as_str() {
+            "fail" => Ok(GetAllPolicy::Fail),
+            "skip" => Ok(GetAllPolicy::Skip),
+            "log" => Ok(GetAllPolicy::Log),
+            x => Err(SynError::new(
+                value.span(),
+                format!(
+                    "excepted \"fail\", \"skip\" or \"log\" got {}{}",
+                    x,
+                    did_you_mean(x, &["fail", "skip", "log"])
+                ),
+            )),
         }
     }
+}
 
-    if properties.is_empty() {
-        None
-    } else {
-        let check_signature_from_header = check_signature_from_header("ss");
-        let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
-        let get_property_from_body_iter = get_string_from_body_iter(&format_ident!("property"));
-        let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
-        let unknown_interface_from_header = unknown_interface_from_header();
-        let create_return_msg_from_header = create_return_msg_from_header();
-        let code = quote!(
-            "Get" => {
-                #check_signature_from_header;
-                #get_interface_from_body_iter;
-                #get_property_from_body_iter;
-                #check_if_no_value_from_body_iter;
-                let value = match interface.as_ref() {
-                    #(#properties)*
-                    _ => #unknown_interface_from_header
-                };
-                let mut msg = #create_return_msg_from_header;
-                msg.add_value(value);
-                return dbus.send(msg);
-            }
-        );
-        Some(code)
+/// Create the code, which creates the `PropertiesChanged` signal of the interface `interface` for
+/// the object at `path`. `changed` has to be a `Vec` of `{sv}` dict entries and `invalidated` a
+/// `Vec` of string values.
+pub(super) fn create_properties_changed_msg_code(
+    path: &TokenStream,
+    interface: &LitStr,
+    changed: &TokenStream,
+    invalidated: &TokenStream,
+) -> TokenStream {
+    quote! {
+        {
+            let mut msg = __dbus_message_parser::Message::signal(
+                #path.clone(),
+                ::std::convert::TryFrom::try_from("org.freedesktop.DBus.Properties").unwrap(),
+                ::std::convert::TryFrom::try_from("PropertiesChanged").unwrap(),
+            );
+            msg.add_value(__dbus_message_parser::Value::String(#interface.to_string()));
+            msg.add_value(__dbus_message_parser::Value::Array(#changed, "{sv}".to_string()));
+            msg.add_value(__dbus_message_parser::Value::Array(#invalidated, "s".to_string()));
+            msg
+        }
     }
 }
 
-fn create_get_all_code(interfaces: &[Interface]) -> Option<TokenStream> {
+/// Create the `Get` method, which returns the property as a variant. The standard interfaces
+/// `standard_interfaces` have no properties, so a `org.freedesktop.DBus.Error.UnknownProperty`
+/// error is returned.
+fn create_get_code(interfaces: &[Interface], standard_interfaces: &[&str]) -> TokenStream {
     let mut properties = Vec::new();
     for interface in interfaces {
-        if let Some(code) = interface.create_get_all_code() {
-            properties.push(code);
-        }
+        properties.push(interface.create_get_code());
     }
-
-    if properties.is_empty() {
-        None
+    let check_signature_from_header = check_signature_from_header("ss");
+    let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
+    let get_property_from_body_iter = get_string_from_body_iter(&format_ident!("property"));
+    let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
+    let unknown_property_from_header = unknown_property_from_header();
+    let unknown_interface_from_body = unknown_interface_from_body();
+    let create_return_msg_from_header = create_return_msg_from_header();
+    let get_value = quote! {
+        match interface.as_ref() {
+            #(#properties)*
+            #(#standard_interfaces)|* => #unknown_property_from_header,
+            _ => #unknown_interface_from_body
+        }
+    };
+    // Without properties, every arm returns an error, so there is no reply
+    let reply = if interfaces.iter().any(Interface::has_properties) {
+        quote! {
+            let value = #get_value;
+            let mut msg = #create_return_msg_from_header;
+            msg.add_value(value);
+            return dbus.send(msg);
+        }
     } else {
-        let check_signature_from_header = check_signature_from_header("s");
-        let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
-        let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
-        let create_return_msg_from_header = create_return_msg_from_header();
-        let unknown_interface_from_header = unknown_interface_from_header();
-        let code = quote!(
-            "GetAll" => {
-                #check_signature_from_header
-                #get_interface_from_body_iter;
-                #check_if_no_value_from_body_iter;
-                let values = match interface.as_ref() {
-                    #(#properties)*
-                    _ => #unknown_interface_from_header
-                };
-                let values = dbus_message_parser::Value::Array(values, "v".to_string());
-                let mut msg = #create_return_msg_from_header;
-                msg.add_value(values);
-                return dbus.send(msg);
-            }
-        );
-        Some(code)
-    }
+        get_value
+    };
+    quote!(
+        "Get" => {
+            #check_signature_from_header;
+            #get_interface_from_body_iter;
+            #get_property_from_body_iter;
+            #check_if_no_value_from_body_iter;
+            #reply
+        }
+    )
 }
 
-fn create_set_code(interfaces: &[Interface]) -> Option<TokenStream> {
+/// Create the `GetAll` method, which returns the readable properties as a dict (`a{sv}`).
+/// The standard interfaces `standard_interfaces` have no properties, so an empty dict is returned.
+fn create_get_all_code(interfaces: &[Interface], standard_interfaces: &[&str]) -> TokenStream {
     let mut properties = Vec::new();
     for interface in interfaces {
-        if let Some(code) = interface.create_set_code() {
-            properties.push(code);
-        }
+        properties.push(interface.create_get_all_code());
     }
+    let check_signature_from_header = check_signature_from_header("s");
+    let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
+    let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
+    let create_return_msg_from_header = create_return_msg_from_header();
+    let unknown_interface_from_body = unknown_interface_from_body();
+    quote!(
+        "GetAll" => {
+            #check_signature_from_header
+            #get_interface_from_body_iter;
+            #check_if_no_value_from_body_iter;
+            let values = match interface.as_ref() {
+                #(#properties)*
+                #(#standard_interfaces)|* => ::std::vec::Vec::new(),
+                _ => #unknown_interface_from_body
+            };
+            let values = __dbus_message_parser::Value::Array(values, "{sv}".to_string());
+            let mut msg = #create_return_msg_from_header;
+            msg.add_value(values);
+            return dbus.send(msg);
+        }
+    )
+}
 
-    if properties.is_empty() {
-        None
-    } else {
-        let check_signature_from_header = check_signature_from_header("ssv");
-        let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
-        let get_property_from_body_iter = get_string_from_body_iter(&format_ident!("property"));
-        let get_value = get_variant_from_body_iter(&format_ident!("variant"));
-        let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
-        let unknown_interface_from_header = unknown_interface_from_header();
-        let create_return_msg_from_header = create_return_msg_from_header();
-        let code = quote!(
-            "Set" => {
-                #check_signature_from_header;
-                #get_interface_from_body_iter;
-                #get_property_from_body_iter;
-                #get_value;
-                #check_if_no_value_from_body_iter;
-                let i = *variant;
-                match interface.as_ref() {
-                    #(#properties)*
-                    _ => #unknown_interface_from_header
-                }
-                let msg = #create_return_msg_from_header;
-                return dbus.send(msg);
-            }
-        );
-        Some(code)
+/// Create the `Set` method, which sets the property from a variant. The standard interfaces
+/// `standard_interfaces` have no properties, so a `org.freedesktop.DBus.Error.UnknownProperty`
+/// error is returned.
+fn create_set_code(interfaces: &[Interface], standard_interfaces: &[&str]) -> TokenStream {
+    let mut properties = Vec::new();
+    for interface in interfaces {
+        properties.push(interface.create_set_code());
     }
+    let check_signature_from_header = check_signature_from_header("ssv");
+    let get_interface_from_body_iter = get_string_from_body_iter(&format_ident!("interface"));
+    let get_property_from_body_iter = get_string_from_body_iter(&format_ident!("property"));
+    let get_value = get_variant_from_body_iter(&format_ident!("variant"));
+    let check_if_no_value_from_body_iter = check_if_no_value_from_body_iter();
+    let unknown_property_from_header = unknown_property_from_header();
+    let unknown_interface_from_body = unknown_interface_from_body();
+    quote!(
+        "Set" => {
+            #check_signature_from_header;
+            #get_interface_from_body_iter;
+            #get_property_from_body_iter;
+            #get_value;
+            #check_if_no_value_from_body_iter;
+            let i = *variant;
+            match interface.as_ref() {
+                #(#properties)*
+                #(#standard_interfaces)|* => #unknown_property_from_header,
+                _ => #unknown_interface_from_body
+            }
+        }
+    )
 }
 
-pub(super) fn create_properties_code(interfaces: &[Interface]) -> Option<TokenStream> {
+/// Create the code of the `org.freedesktop.DBus.Properties` interface. The interface is also
+/// generated, if the object has no properties.
+pub(super) fn create_properties_code(
+    interfaces: &[Interface],
+    standard_interfaces: &[&str],
+) -> TokenStream {
     let get_member_from_header = get_member_from_header();
-    let get = create_get_code(interfaces);
-    let get_all = create_get_all_code(interfaces);
-    let set = create_set_code(interfaces);
+    let get = create_get_code(interfaces, standard_interfaces);
+    let get_all = create_get_all_code(interfaces, standard_interfaces);
+    let set = create_set_code(interfaces, standard_interfaces);
     let unknown_member_from_header = unknown_member_from_header();
-    if get.is_none() && get_all.is_none() && set.is_none() {
-        None
-    } else {
-        let code = quote! {
-            "org.freedesktop.DBus.Properties" => {
-                match #get_member_from_header {
-                    #get
-                    #get_all
-                    #set
-                    _ => #unknown_member_from_header
-                }
+    quote! {
+        "org.freedesktop.DBus.Properties" => {
+            match #get_member_from_header {
+                #get
+                #get_all
+                #set
+                _ => #unknown_member_from_header
             }
-        };
-        Some(code)
+        }
     }
 }
@@ -1,117 +1,977 @@
-use crate::code::check_result;
+use crate::annotation::{annotations_to_introspect, split_annotations, Annotation};
+use crate::check::{
+    create_argument_check, create_output_check, get_argument_check_function,
+    get_output_check_function, rust_type_to_string,
+};
+use crate::code::{check_result, create_error_code, create_return_msg_from_header};
+use crate::doc::{doc_to_introspect, split_doc};
 use crate::helper::{
-    get_ident_from_path, get_lit_str_from_lit, get_lit_str_from_option_nested_meta,
-    get_meta_name_value_from_nested_meta,
+    did_you_mean, get_default_emit_function_name, get_ident_from_lit, get_ident_from_path,
+    get_lit_bool_from_lit, get_lit_str_from_lit, get_lit_str_from_option_nested_meta,
+    get_meta_name_value_from_nested_meta, get_named_arguments, get_required_argument,
+    is_named_syntax,
 };
 use crate::introspectable::Introspectable;
-use crate::signature::SignatureIterator;
+use crate::name::check_member_name;
+use crate::properties::{create_properties_changed_msg_code, GetAllPolicy};
+use crate::signature::{signature_from_type, SignatureIterator};
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use std::convert::TryFrom;
 use syn::spanned::Spanned;
-use syn::{Error as SynError, LitStr, MetaList, NestedMeta, Result as SynResult};
+use syn::{
+    Error as SynError, Field, Lit, LitBool, LitStr, MetaList, NestedMeta, Result as SynResult,
+};
+
+/// The value of the `org.freedesktop.DBus.Property.EmitsChangedSignal` annotation.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum EmitsChanged {
+    /// The `PropertiesChanged` signal is emitted with the new value.
+    True,
+    /// The `PropertiesChanged` signal is emitted without the new value.
+    Invalidates,
+    /// The property never changes.
+    Const,
+    /// The `PropertiesChanged` signal is not emitted.
+    False,
+}
+
+impl EmitsChanged {
+    fn as_str(self) -> &'static str {
+        match self {
+            EmitsChanged::True => "true",
+            EmitsChanged::Invalidates => "invalidates",
+            EmitsChanged::Const => "const",
+            EmitsChanged::False => "false",
+        }
+    }
+}
+
+impl TryFrom<&Lit> for EmitsChanged {
+    type Error = SynError;
+
+    fn try_from(lit: &Lit) -> Result<Self, Self::Error> {
+        if let Lit::Bool(lit_bool) = lit {
+            return if lit_bool.value {
+                Ok(EmitsChanged::True)
+            } else {
+                Ok(EmitsChanged::False)
+            };
+        }
+        let value = get_lit_str_from_lit(lit)?;
+        match value.value().as_str() {
+            "true" => Ok(EmitsChanged::True),
+            "invalidates" => Ok(EmitsChanged::Invalidates),
+            "const" => Ok(EmitsChanged::Const),
+            "false" => Ok(EmitsChanged::False),
+            x => Err(SynError::new(
+                value.span(),
+                format!(
+                    "excepted \"true\", \"invalidates\", \"const\" or \"false\" got {}{}",
+                    x,
+                    did_you_mean(x, &["true", "invalidates", "const", "false"])
+                ),
+            )),
+        }
+    }
+}
+
+/// How the value of a property is read or written.
+pub(super) enum Accessor {
+    /// An async function, which gets or sets the value.
+    Function(Ident),
+    /// A field, which contains the value.
+    Field(Ident),
+}
+
+impl Accessor {
+    /// Create the expression, which reads the value as a `Result<T, (Error, String)>`.
+    fn create_get_code(&self) -> TokenStream {
+        match self {
+            Accessor::Function(function) => quote! {
+                self.#function(&dbus, &header).await
+            },
+            Accessor::Field(field) => quote! {
+                ::std::result::Result::<_, (__dbus_message_parser::Error, ::std::string::String)>::Ok(
+                    ::std::clone::Clone::clone(&self.#field)
+                )
+            },
+        }
+    }
+
+    /// Create the expression, which writes `value` and returns a `Result<(), (Error, String)>`.
+    fn create_set_code(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Accessor::Function(function) => quote! {
+                self.#function(&dbus, &header, #value).await
+            },
+            Accessor::Field(field) => quote! {
+                {
+                    self.#field = #value;
+                    ::std::result::Result::<(), (__dbus_message_parser::Error, ::std::string::String)>::Ok(())
+                }
+            },
+        }
+    }
+}
 
 pub(super) struct Property {
     name: LitStr,
+    get: Option<Accessor>,
+    set: Option<Accessor>,
+    /// The signature is `None`, if it is inferred from the type of the field.
+    signature: Option<(String, TokenStream, TokenStream, TokenStream)>,
+    emits_changed: Option<EmitsChanged>,
+    emit_on_set: Option<LitBool>,
+    /// The name of the emit function, which is chosen by `emit_fn`.
+    emit_function: Option<Ident>,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
+}
+
+/// The name of the annotation, which is written for the `emits_changed` option.
+pub(super) static EMITS_CHANGED_SIGNAL: &str = "org.freedesktop.DBus.Property.EmitsChangedSignal";
+
+/// The options of a property, which are collected while the attribute is parsed.
+#[derive(Default)]
+struct PropertyOptions {
     get: Option<Ident>,
     set: Option<Ident>,
-    signature: (String, TokenStream, TokenStream, TokenStream),
+    field: Option<Ident>,
+    access: Option<LitStr>,
+    emits_changed: Option<EmitsChanged>,
+    emit_on_set: Option<LitBool>,
+    emit_function: Option<Ident>,
 }
 
-fn create_property_code(
-    nested_meta: &NestedMeta,
-    get: &mut Option<Ident>,
-    set: &mut Option<Ident>,
-) -> SynResult<()> {
+impl PropertyOptions {
+    /// Create the property from the options.
+    fn into_property(
+        self,
+        meta_list: &MetaList,
+        name: LitStr,
+        signature: Option<(String, TokenStream, TokenStream, TokenStream)>,
+    ) -> SynResult<Property> {
+        let (get, set) = if let Some(field) = self.field {
+            if self.get.is_some() || self.set.is_some() {
+                return Err(SynError::new(
+                    field.span(),
+                    "field cannot be combined with a \"get\" or \"set\" function",
+                ));
+            }
+            let (read, write) = match self.access {
+                Some(access) => match access.value().as_str() {
+                    "read" => (true, false),
+                    "write" => (false, true),
+                    "readwrite" => (true, true),
+                    x => {
+                        return Err(SynError::new(
+                            access.span(),
+                            format!(
+                                "excepted \"read\", \"write\" or \"readwrite\" got {}{}",
+                                x,
+                                did_you_mean(x, &["read", "write", "readwrite"])
+                            ),
+                        ))
+                    }
+                },
+                None => (true, true),
+            };
+            let get = if read {
+                Some(Accessor::Field(field.clone()))
+            } else {
+                None
+            };
+            let set = if write {
+                Some(Accessor::Field(field))
+            } else {
+                None
+            };
+            (get, set)
+        } else {
+            if let Some(access) = self.access {
+                return Err(SynError::new(access.span(), "access needs a field"));
+            }
+            if self.get.is_none() && self.set.is_none() {
+                return Err(SynError::new(
+                    meta_list.nested.span(),
+                    "no \"get\" or \"set\" function defined",
+                ));
+            }
+            (
+                self.get.map(Accessor::Function),
+                self.set.map(Accessor::Function),
+            )
+        };
+        if signature.is_none() && !matches!(get.as_ref().or(set.as_ref()), Some(Accessor::Field(_)))
+        {
+            return Err(SynError::new(meta_list.span(), "no \"type\" defined"));
+        }
+
+        let property = Property {
+            name,
+            get,
+            set,
+            signature,
+            emits_changed: self.emits_changed,
+            emit_on_set: self.emit_on_set,
+            emit_function: self.emit_function,
+            annotations: Vec::new(),
+            doc: None,
+        };
+        property.check_emit_on_set()?;
+        property.check_emit_function()?;
+        Ok(property)
+    }
+}
+
+fn create_property_code(nested_meta: &NestedMeta, options: &mut PropertyOptions) -> SynResult<()> {
     let meta_name_value = get_meta_name_value_from_nested_meta(nested_meta)?;
     let function = get_ident_from_path(&meta_name_value.path)?;
-    let operation = get_lit_str_from_lit(&meta_name_value.lit)?;
+    let lit = &meta_name_value.lit;
+    // `field = "get"` is a get function with the name `field`. The same holds for the other
+    // options, e.g. `emit_on_set = "set"` is a set function with the name `emit_on_set`
+    let is_operation =
+        matches!(lit, Lit::Str(lit_str) if lit_str.value() == "get" || lit_str.value() == "set");
+    match function.to_string().as_str() {
+        "emits_changed" if !is_operation => {
+            if options.emits_changed.is_some() {
+                return Err(SynError::new(
+                    function.span(),
+                    "emits_changed is defined twice",
+                ));
+            }
+            options.emits_changed = Some(EmitsChanged::try_from(lit)?);
+            return Ok(());
+        }
+        "emit_on_set" if !is_operation => {
+            if options.emit_on_set.is_some() {
+                return Err(SynError::new(
+                    function.span(),
+                    "emit_on_set is defined twice",
+                ));
+            }
+            options.emit_on_set = Some(get_lit_bool_from_lit(lit)?);
+            return Ok(());
+        }
+        "emit_fn" if !is_operation => {
+            if options.emit_function.is_some() {
+                return Err(SynError::new(function.span(), "emit_fn is defined twice"));
+            }
+            options.emit_function = Some(get_ident_from_lit(lit)?);
+            return Ok(());
+        }
+        "field" if !is_operation => {
+            if options.field.is_some() {
+                return Err(SynError::new(function.span(), "field is defined twice"));
+            }
+            options.field = Some(get_ident_from_lit(lit)?);
+            return Ok(());
+        }
+        "access" if !is_operation => {
+            if options.access.is_some() {
+                return Err(SynError::new(function.span(), "access is defined twice"));
+            }
+            options.access = Some(get_lit_str_from_lit(lit)?);
+            return Ok(());
+        }
+        _ => {}
+    }
+    let operation = get_lit_str_from_lit(lit)?;
     match operation.value().as_str() {
         "get" => {
-            if get.is_some() {
+            if options.get.is_some() {
                 return Err(SynError::new(operation.span(), "get is defined twice"));
             }
-            *get = Some(function);
+            options.get = Some(function);
             Ok(())
         }
         "set" => {
-            if set.is_some() {
+            if options.set.is_some() {
                 return Err(SynError::new(operation.span(), "set is defined twice"));
             }
-            *set = Some(function);
+            options.set = Some(function);
             Ok(())
         }
         x => Err(SynError::new(
             operation.span(),
-            &format!("excepted \"get\" or \"set\" got {}", x),
+            format!(
+                "excepted \"get\" or \"set\" got {}{}",
+                x,
+                did_you_mean(x, &["get", "set"])
+            ),
         )),
     }
 }
 
+/// Parse the signature of a property, which has to be a single complete type.
+pub(super) fn get_property_signature(
+    signature: &LitStr,
+) -> SynResult<(String, TokenStream, TokenStream, TokenStream)> {
+    let mut signature_iter = SignatureIterator::from(signature);
+    let result = if let Some(result) = signature_iter.next() {
+        result?
+    } else {
+        return Err(SynError::new(
+            signature.span(),
+            "excepted only one signature type",
+        ));
+    };
+
+    if signature_iter.next().is_some() {
+        return Err(SynError::new(
+            signature.span(),
+            "excepted only one signature type",
+        ));
+    }
+    Ok(result)
+}
+
 impl Property {
+    pub(super) fn new(
+        name: LitStr,
+        get: Option<Ident>,
+        set: Option<Ident>,
+        signature: (String, TokenStream, TokenStream, TokenStream),
+    ) -> Property {
+        Property {
+            name,
+            get: get.map(Accessor::Function),
+            set: set.map(Accessor::Function),
+            signature: Some(signature),
+            emits_changed: None,
+            emit_on_set: None,
+            emit_function: None,
+            annotations: Vec::new(),
+            doc: None,
+        }
+    }
+
+    pub(super) fn set_doc(&mut self, doc: Option<LitStr>) {
+        self.doc = doc;
+    }
+
+    /// Set the annotations of the property. The `EmitsChangedSignal` annotation cannot be set,
+    /// if the `emits_changed` option is used.
+    pub(super) fn set_annotations(&mut self, annotations: Vec<Annotation>) -> SynResult<()> {
+        if self.emits_changed.is_some() {
+            if let Some(annotation) = annotations
+                .iter()
+                .find(|annotation| annotation.get_name().value() == EMITS_CHANGED_SIGNAL)
+            {
+                return Err(SynError::new(
+                    annotation.get_name().span(),
+                    "the annotation cannot be combined with emits_changed",
+                ));
+            }
+        }
+        self.annotations = annotations;
+        Ok(())
+    }
+
+    pub(super) fn set_emits_changed(&mut self, emits_changed: EmitsChanged) {
+        self.emits_changed = Some(emits_changed);
+    }
+
+    fn get_signature(&self) -> &(String, TokenStream, TokenStream, TokenStream) {
+        self.signature
+            .as_ref()
+            .expect("the signature of the field is not inferred")
+    }
+
+    /// Get the field, which contains the value of the property.
+    fn get_field(&self) -> Option<&Ident> {
+        match (&self.get, &self.set) {
+            (Some(Accessor::Field(field)), _) | (_, Some(Accessor::Field(field))) => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Infer the signature from the type of the field, if no signature is defined.
+    pub(super) fn infer_signature(&mut self, fields: &[&Field]) -> SynResult<()> {
+        if self.signature.is_some() {
+            return Ok(());
+        }
+        let field = self.get_field().expect("no signature and no field");
+        let rust_type = fields
+            .iter()
+            .find(|f| f.ident.as_ref() == Some(field))
+            .map(|f| &f.ty)
+            .ok_or_else(|| {
+                SynError::new(
+                    field.span(),
+                    format!("the field `{}` does not exist", field),
+                )
+            })?;
+        let signature = match signature_from_type(rust_type) {
+            Ok(signature) => signature,
+            Err(_) => {
+                return Err(SynError::new(
+                    field.span(),
+                    format!(
+                        "could not infer the signature of the field `{}`, define \"type\"",
+                        field
+                    ),
+                ))
+            }
+        };
+        let signature = LitStr::new(&signature, field.span());
+        self.signature = Some(get_property_signature(&signature)?);
+        Ok(())
+    }
+
+    /// Check if the `emit_on_set` option can be used. The property needs a set function and the
+    /// `PropertiesChanged` signal has to be emitted for this property.
+    fn check_emit_on_set(&self) -> SynResult<()> {
+        if let Some(emit_on_set) = &self.emit_on_set {
+            if !emit_on_set.value {
+                return Ok(());
+            }
+            if self.set.is_none() {
+                return Err(SynError::new(
+                    emit_on_set.span(),
+                    "emit_on_set needs a \"set\" function",
+                ));
+            }
+            if !self.has_emit_function() {
+                return Err(SynError::new(
+                    emit_on_set.span(),
+                    "emit_on_set needs emits_changed to be true or invalidates",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if the `PropertiesChanged` signal is emitted after a successful `Set` call. If the
+    /// property does not define `emit_on_set`, then `default` is used, if the signal can be
+    /// emitted for this property.
+    fn is_emit_on_set(&self, default: bool) -> bool {
+        match &self.emit_on_set {
+            Some(emit_on_set) => emit_on_set.value,
+            None => default && self.set.is_some() && self.has_emit_function(),
+        }
+    }
+
+    pub(super) fn get_name(&self) -> &LitStr {
+        &self.name
+    }
+
+    pub(super) fn is_readable(&self) -> bool {
+        self.get.is_some()
+    }
+
+    /// Check if the `PropertiesChanged` signal is emitted for this property, so an emit function
+    /// is generated.
+    fn has_emit_function(&self) -> bool {
+        match self.emits_changed.unwrap_or(EmitsChanged::True) {
+            EmitsChanged::True | EmitsChanged::Invalidates => true,
+            EmitsChanged::Const | EmitsChanged::False => false,
+        }
+    }
+
+    /// Check if the `emit_fn` option can be used. The `PropertiesChanged` signal has to be
+    /// emitted for this property.
+    fn check_emit_function(&self) -> SynResult<()> {
+        match &self.emit_function {
+            Some(emit_function) if !self.has_emit_function() => Err(SynError::new(
+                emit_function.span(),
+                "emit_fn needs emits_changed = true or emits_changed = invalidates",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the name of the emit function, if it is chosen by `emit_fn`.
+    pub(super) fn get_emit_function(&self) -> Option<&Ident> {
+        self.emit_function.as_ref()
+    }
+
+    /// Get the name of the function, which emits the `PropertiesChanged` signal for this property
+    /// of the interface `interface`. By default, the name is qualified by the interface.
+    /// There is no such function, if the signal is not emitted for this property.
+    fn get_emit_function_name(&self, interface: &LitStr) -> Option<Ident> {
+        if !self.has_emit_function() {
+            return None;
+        }
+        if let Some(emit_function) = &self.emit_function {
+            Some(emit_function.clone())
+        } else {
+            let function = format!(
+                "{}_changed",
+                get_default_emit_function_name(interface, &self.name)
+            );
+            Some(Ident::new(&function, self.name.span()))
+        }
+    }
+
+    /// Create the function, which emits the `PropertiesChanged` signal for this property of the
+    /// interface `interface`. If the annotation is `true`, then the function takes the new value.
+    /// If the annotation is `invalidates`, then the property is only invalidated.
+    pub(super) fn create_emit_function_code(&self, interface: &LitStr) -> Option<TokenStream> {
+        let function = self.get_emit_function_name(interface)?;
+        let name = &self.name;
+        let path = quote! { path };
+        let doc = format!(
+            "Emit the `org.freedesktop.DBus.Properties.PropertiesChanged` signal for the property \
+            `{}` of the interface `{}` from the object at `path`.",
+            name.value(),
+            interface.value()
+        );
+        let code = if self.emits_changed == Some(EmitsChanged::Invalidates) {
+            let changed = quote! { ::std::vec::Vec::new() };
+            let invalidated = quote! {
+                ::std::vec![__dbus_message_parser::Value::String(#name.to_string())]
+            };
+            let msg = create_properties_changed_msg_code(&path, interface, &changed, &invalidated);
+            quote! {
+                #[doc = #doc]
+                pub fn #function(
+                    &self,
+                    dbus: &__dbus_async::DBus,
+                    path: &__dbus_message_parser::ObjectPath,
+                ) -> __dbus_async::DBusResult<()> {
+                    let msg = #msg;
+                    dbus.send(msg)
+                }
+            }
+        } else {
+            let (_, rust_type, _, rust_to_value) = self.get_signature();
+            let changed = quote! {
+                {
+                    let v = ::std::boxed::Box::new(#rust_to_value);
+                    let name = __dbus_message_parser::Value::String(#name.to_string());
+                    ::std::vec![__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((name, __dbus_message_parser::Value::Variant(v))))]
+                }
+            };
+            let invalidated = quote! { ::std::vec::Vec::new() };
+            let msg = create_properties_changed_msg_code(&path, interface, &changed, &invalidated);
+            quote! {
+                #[doc = #doc]
+                pub fn #function(
+                    &self,
+                    dbus: &__dbus_async::DBus,
+                    path: &__dbus_message_parser::ObjectPath,
+                    value: #rust_type,
+                ) -> __dbus_async::DBusResult<()> {
+                    let i: #rust_type = value;
+                    let msg = #msg;
+                    dbus.send(msg)
+                }
+            }
+        };
+        Some(code)
+    }
+
+    /// Parse the named-key syntax:
+    /// `property(name = "Name", type = "signature", get = function, set = function)` or
+    /// `property(name = "Name", type = "signature", field = field, access = read)`.
+    /// The type can be omitted for a field, if it can be inferred.
+    fn try_from_named(meta_list: &MetaList) -> SynResult<Property> {
+        let mut name = None;
+        let mut signature = None;
+        let mut options = PropertyOptions::default();
+        for (key, lit) in get_named_arguments(
+            meta_list,
+            &[
+                "name",
+                "type",
+                "get",
+                "set",
+                "field",
+                "access",
+                "emits_changed",
+                "emit_on_set",
+                "emit_fn",
+            ],
+        )? {
+            match key.to_string().as_ref() {
+                "name" => name = Some(get_lit_str_from_lit(lit)?),
+                "type" => signature = Some(get_property_signature(&get_lit_str_from_lit(lit)?)?),
+                "get" => options.get = Some(get_ident_from_lit(lit)?),
+                "set" => options.set = Some(get_ident_from_lit(lit)?),
+                "field" => options.field = Some(get_ident_from_lit(lit)?),
+                "access" => options.access = Some(get_lit_str_from_lit(lit)?),
+                "emits_changed" => options.emits_changed = Some(EmitsChanged::try_from(lit)?),
+                "emit_on_set" => options.emit_on_set = Some(get_lit_bool_from_lit(lit)?),
+                "emit_fn" => options.emit_function = Some(get_ident_from_lit(lit)?),
+                _ => unreachable!(),
+            }
+        }
+
+        let name = get_required_argument(meta_list, "name", name)?;
+        check_member_name(&name, "property")?;
+        options.into_property(meta_list, name, signature)
+    }
+
+    /// Parse the positional syntax: `property("Name", "signature", function = "get", ...)`.
+    fn try_from_positional(meta_list: &MetaList) -> SynResult<Property> {
+        let mut options = PropertyOptions::default();
+        let nested_iter = &mut meta_list.nested.iter();
+        // Get the name of the property
+        let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        check_member_name(&name, "property")?;
+
+        // Get the signature
+        let signature = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+        let signature = get_property_signature(&signature)?;
+
+        // Get the functions and the options
+        for nested_meta in nested_iter {
+            create_property_code(nested_meta, &mut options)?;
+        }
+        options.into_property(meta_list, name, Some(signature))
+    }
+
+    /// Get the key of the check functions of a field. The field is prefixed, so the check
+    /// functions do not collide with the check functions of a function with the same name.
+    fn get_field_check_key(field: &Ident) -> Ident {
+        format_ident!("field_{}", field)
+    }
+
+    /// Create the checks, if the get and set function or the field have the type of the
+    /// signature.
+    /// It returns the names of the check functions and the items of the checks.
+    pub(super) fn create_check_code(&self) -> Vec<(Ident, TokenStream)> {
+        let name = self.name.value();
+        let span = self.name.span();
+        let (signature, rust_type, _, _) = self.get_signature();
+        let rust_type_string = rust_type_to_string(rust_type);
+
+        let mut checks = Vec::new();
+        if let Some(field) = self.get_field() {
+            let message = format!(
+                "property `{}` is `{}` ({}), but the field `{}` is `{{Self}}`",
+                name, signature, rust_type_string, field
+            );
+            let key = Property::get_field_check_key(field);
+            checks.push(create_argument_check(
+                &key,
+                0,
+                rust_type,
+                &message,
+                field.span(),
+            ));
+            checks.push(create_output_check(&key, rust_type, &message, field.span()));
+        }
+        if let Some(Accessor::Function(function)) = &self.get {
+            let message = format!(
+                "property `{}` is `{}` ({}), but `{}` returns `{{Self}}`",
+                name, signature, rust_type_string, function
+            );
+            checks.push(create_output_check(function, rust_type, &message, span));
+        }
+        if let Some(Accessor::Function(function)) = &self.set {
+            let message = format!(
+                "property `{}` is `{}` ({}), but `{}` takes `{{Self}}`",
+                name, signature, rust_type_string, function
+            );
+            checks.push(create_argument_check(
+                function, 0, rust_type, &message, span,
+            ));
+            let message = format!("`{}` has to return nothing", function);
+            checks.push(create_output_check(
+                function,
+                &quote! { () },
+                &message,
+                span,
+            ));
+        }
+        checks
+    }
+
+    /// Create the expression, which reads the value by `get` as a `Result<T, (Error, String)>`.
+    /// The value is passed through the check function of the get function or the field.
+    fn create_checked_get_code(&self, get: &Accessor) -> TokenStream {
+        let (output, span) = match get {
+            Accessor::Function(function) => {
+                let span = self.name.span();
+                (get_output_check_function(function, span), span)
+            }
+            Accessor::Field(field) => {
+                let key = Property::get_field_check_key(field);
+                (get_output_check_function(&key, field.span()), field.span())
+            }
+        };
+        let get = get.create_get_code();
+        quote_spanned! {span=>
+            {
+                let result = #get;
+                #output(result)
+            }
+        }
+    }
+
+    /// Create the expression, which writes `value` by `set` and returns a
+    /// `Result<(), (Error, String)>`.
+    /// The value and the result are passed through the check functions of the set function or the
+    /// field.
+    fn create_checked_set_code(&self, set: &Accessor) -> TokenStream {
+        match set {
+            Accessor::Function(function) => {
+                let span = self.name.span();
+                let argument = get_argument_check_function(function, 0, span);
+                let output = get_output_check_function(function, span);
+                let set = set.create_set_code(quote_spanned! {span=> #argument(value) });
+                quote_spanned! {span=>
+                    {
+                        let result = #set;
+                        #output(result)
+                    }
+                }
+            }
+            Accessor::Field(field) => {
+                let span = field.span();
+                let key = Property::get_field_check_key(field);
+                let argument = get_argument_check_function(&key, 0, span);
+                set.create_set_code(quote_spanned! {span=> #argument(value) })
+            }
+        }
+    }
+
+    /// Get the documentation of the get or the set function in the trait of the interface
+    /// `interface`. The documentation of the property is added in front of the description.
+    fn get_trait_function_docs(&self, interface: &LitStr, verb: &str) -> Vec<String> {
+        let mut docs = vec![format!(
+            "{} the value of the property `{}` of the interface `{}`.",
+            verb,
+            self.name.value(),
+            interface.value()
+        )];
+        if let Some(doc) = &self.doc {
+            docs.insert(0, String::new());
+            docs.insert(0, format!(" {}", doc.value()));
+        }
+        docs
+    }
+
+    /// Create the declarations of the get and the set function in the trait of the interface
+    /// `interface`. There are no declarations for a field-backed property.
+    pub(super) fn create_trait_functions_code(&self, interface: &LitStr) -> Vec<TokenStream> {
+        let (_, rust_type, _, _) = self.get_signature();
+        let mut functions = Vec::new();
+        if let Some(Accessor::Function(function)) = &self.get {
+            let docs = self.get_trait_function_docs(interface, "Get");
+            functions.push(quote! {
+                #(#[doc = #docs])*
+                async fn #function(
+                    &mut self,
+                    dbus: &__dbus_async::DBus,
+                    msg_header: &__dbus_message_parser::MessageHeader,
+                ) -> ::std::result::Result<#rust_type, (__dbus_message_parser::Error, ::std::string::String)>;
+            });
+        }
+        if let Some(Accessor::Function(function)) = &self.set {
+            let docs = self.get_trait_function_docs(interface, "Set");
+            functions.push(quote! {
+                #(#[doc = #docs])*
+                async fn #function(
+                    &mut self,
+                    dbus: &__dbus_async::DBus,
+                    msg_header: &__dbus_message_parser::MessageHeader,
+                    value: #rust_type,
+                ) -> ::std::result::Result<(), (__dbus_message_parser::Error, ::std::string::String)>;
+            });
+        }
+        functions
+    }
+
     pub(super) fn create_get_code(&self) -> TokenStream {
         let name = &self.name;
-        if let Some(function) = &self.get {
-            let (_, _, _, rust_to_value) = &self.signature;
+        if let Some(get) = &self.get {
+            let (_, _, _, rust_to_value) = self.get_signature();
             let check_result = check_result();
+            let get = self.create_checked_get_code(get);
             quote! {
                 #name => {
-                    let result = self.#function(&dbus, &header).await;
+                    let result = #get;
                     let i = #check_result;
-                    let v = std::boxed::Box::new(#rust_to_value);
-                    dbus_message_parser::Value::Variant(v)
+                    let v = ::std::boxed::Box::new(#rust_to_value);
+                    __dbus_message_parser::Value::Variant(v)
                 }
             }
         } else {
+            let error = create_error_code(
+                "InvalidArgs",
+                quote! {
+                    ::std::format!(
+                        "The property `{}` of the interface `{}` is write only",
+                        #name,
+                        interface
+                    )
+                },
+            );
             quote! {
-                #name => {
-                    let msg = header.error(
-                        std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.Property".to_string()).unwrap(),
-                        "This property is write only".to_string());
-                    return dbus.send(msg);
-                }
+                #name => #error
             }
         }
     }
 
-    pub(super) fn create_get_all_code(&self) -> Option<TokenStream> {
-        if let Some(function) = &self.get {
-            let (_, _, _, rust_to_value) = &self.signature;
-            let check_result = check_result();
-            let code = quote! {
+    /// Create the code, which pushes the property as a dict entry (`{sv}`) to `o`.
+    /// If the get function fails, then the policy `policy` of the interface `interface` is applied.
+    /// `fail` is the expression of the `Fail` policy, which gets the value from `result`.
+    fn create_push_dict_entry_code(
+        &self,
+        interface: &LitStr,
+        policy: GetAllPolicy,
+        fail: TokenStream,
+    ) -> Option<TokenStream> {
+        let get = self.get.as_ref()?;
+        let (_, _, _, rust_to_value) = self.get_signature();
+        let name = &self.name;
+        let get = self.create_checked_get_code(get);
+        let push = quote! {
+            let v = ::std::boxed::Box::new(#rust_to_value);
+            let name = __dbus_message_parser::Value::String(#name.to_string());
+            o.push(__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((name, __dbus_message_parser::Value::Variant(v)))));
+        };
+        let code = match policy {
+            GetAllPolicy::Fail => quote! {
                 {
-                    let result = self.#function(&dbus, &header).await;
-                    let i = #check_result;
-                    let v = std::boxed::Box::new(#rust_to_value);
-                    dbus_message_parser::Value::Variant(v)
+                    let result = #get;
+                    let i = #fail;
+                    #push
                 }
-            };
-            Some(code)
-        } else {
-            None
+            },
+            GetAllPolicy::Skip => quote! {
+                if let ::std::result::Result::Ok(i) = #get {
+                    #push
+                }
+            },
+            GetAllPolicy::Log => {
+                let text = format!(
+                    "GetAll: could not get the property `{}` of the interface `{}`: {{}}: {{}}",
+                    name.value(),
+                    interface.value()
+                );
+                quote! {
+                    match #get {
+                        ::std::result::Result::Ok(i) => {
+                            #push
+                        }
+                        ::std::result::Result::Err((name, message)) => {
+                            __log::error!(#text, name, message);
+                        }
+                    }
+                }
+            }
+        };
+        Some(code)
+    }
+
+    /// Create the code of `GetAll`, which pushes the property as a dict entry (`{sv}`) to `o`.
+    /// If the get function fails, then the policy `policy` of the interface `interface` is applied.
+    /// For the `Fail` policy, the error is returned to the caller.
+    pub(super) fn create_get_all_code(
+        &self,
+        interface: &LitStr,
+        policy: GetAllPolicy,
+    ) -> Option<TokenStream> {
+        self.create_push_dict_entry_code(interface, policy, check_result())
+    }
+
+    /// Create the code of `GetManagedObjects` and `InterfacesAdded`, which pushes the property as
+    /// a dict entry (`{sv}`) to `o`.
+    /// If the get function fails, then the policy `policy` of the interface `interface` is applied.
+    /// For the `Fail` policy, the error is returned by the function.
+    pub(super) fn create_dict_entry_code(
+        &self,
+        interface: &LitStr,
+        policy: GetAllPolicy,
+    ) -> Option<TokenStream> {
+        self.create_push_dict_entry_code(interface, policy, quote! { result? })
+    }
+
+    /// Create the code, which emits the `PropertiesChanged` signal after a successful `Set` call.
+    /// The new value is read by the get function. If there is no get function or the get function
+    /// fails, then the property is invalidated.
+    fn create_emit_on_set_code(&self, interface: &LitStr) -> TokenStream {
+        let name = &self.name;
+        let path = quote! { path };
+        let changed = quote! { changed };
+        let invalidated = quote! { invalidated };
+        let msg = create_properties_changed_msg_code(&path, interface, &changed, &invalidated);
+        let invalidate = quote! {
+            (
+                ::std::vec::Vec::new(),
+                ::std::vec![__dbus_message_parser::Value::String(#name.to_string())],
+            )
+        };
+        let changed_invalidated = match (&self.get, self.emits_changed) {
+            (Some(get), None) | (Some(get), Some(EmitsChanged::True)) => {
+                let (_, _, _, rust_to_value) = self.get_signature();
+                let get = self.create_checked_get_code(get);
+                quote! {
+                    match #get {
+                        ::std::result::Result::Ok(i) => {
+                            let v = ::std::boxed::Box::new(#rust_to_value);
+                            let name = __dbus_message_parser::Value::String(#name.to_string());
+                            (
+                                ::std::vec![__dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((name, __dbus_message_parser::Value::Variant(v))))],
+                                ::std::vec::Vec::new(),
+                            )
+                        }
+                        ::std::result::Result::Err(_) => #invalidate,
+                    }
+                }
+            }
+            _ => invalidate,
+        };
+        quote! {
+            if let ::std::option::Option::Some(path) = header.get_path() {
+                let (changed, invalidated) = #changed_invalidated;
+                let msg = #msg;
+                dbus.send(msg)?;
+            }
         }
     }
 
-    pub(super) fn create_set_code(&self) -> TokenStream {
+    /// Create the code, which sets the property. If `emit_on_set` is enabled, then the
+    /// `PropertiesChanged` signal is emitted after the return message.
+    pub(super) fn create_set_code(&self, interface: &LitStr, emit_on_set: bool) -> TokenStream {
         let name = &self.name;
-        if let Some(function) = &self.set {
-            let (_, rust_type, value_to_rust, _) = &self.signature;
+        if let Some(set) = &self.set {
+            let (_, rust_type, value_to_rust, _) = self.get_signature();
             let check_result = check_result();
-            quote! {
-                #name => {
-                    let value: #rust_type = #value_to_rust;
-                    let result = self.#function(&dbus, &header, value).await;
-                    #check_result;
+            let create_return_msg_from_header = create_return_msg_from_header();
+            let set = self.create_checked_set_code(set);
+            if self.is_emit_on_set(emit_on_set) {
+                let emit_on_set = self.create_emit_on_set_code(interface);
+                quote! {
+                    #name => {
+                        let value: #rust_type = #value_to_rust;
+                        let result = #set;
+                        #check_result;
+                        let msg = #create_return_msg_from_header;
+                        dbus.send(msg)?;
+                        #emit_on_set
+                        return ::std::result::Result::Ok(());
+                    }
+                }
+            } else {
+                quote! {
+                    #name => {
+                        let value: #rust_type = #value_to_rust;
+                        let result = #set;
+                        #check_result;
+                        let msg = #create_return_msg_from_header;
+                        return dbus.send(msg);
+                    }
                 }
             }
         } else {
+            let error = create_error_code(
+                "PropertyReadOnly",
+                quote! {
+                    ::std::format!(
+                        "The property `{}` of the interface `{}` is read only",
+                        #name,
+                        interface
+                    )
+                },
+            );
             quote! {
-                #name => {
-                    let msg = header.error(
-                        std::convert::TryFrom::try_from("org.freedesktop.DBus.Error.Property".to_string()).unwrap(),
-                        "This property is read only".to_string());
-                    return dbus.send(msg);
-                }
+                #name => #error
             }
         }
     }
@@ -128,12 +988,31 @@ impl Introspectable for Property {
             access += "write";
         }
 
-        *xml += &format!(
-            "    <property type=\"{}\" name=\"{}\" access=\"{}\"/>\n",
-            self.signature.0,
-            self.name.value(),
-            access
-        );
+        doc_to_introspect(self.doc.as_ref(), xml, "    ");
+        if self.emits_changed.is_some() || !self.annotations.is_empty() {
+            *xml += &format!(
+                "    <property type=\"{}\" name=\"{}\" access=\"{}\">\n",
+                self.get_signature().0,
+                self.name.value(),
+                access
+            );
+            if let Some(emits_changed) = self.emits_changed {
+                *xml += &format!(
+                    "      <annotation name=\"{}\" value=\"{}\"/>\n",
+                    EMITS_CHANGED_SIGNAL,
+                    emits_changed.as_str()
+                );
+            }
+            annotations_to_introspect(&self.annotations, xml, "      ");
+            *xml += "    </property>\n";
+        } else {
+            *xml += &format!(
+                "    <property type=\"{}\" name=\"{}\" access=\"{}\"/>\n",
+                self.get_signature().0,
+                self.name.value(),
+                access
+            );
+        }
     }
 }
 
@@ -141,9 +1020,6 @@ impl TryFrom<&MetaList> for Property {
     type Error = SynError;
 
     fn try_from(meta_list: &MetaList) -> Result<Self, Self::Error> {
-        let mut get = None;
-        let mut set = None;
-
         // Get the ident and check if it is equal "property"
         let meta_list_type = get_ident_from_path(&meta_list.path)?;
         if meta_list_type != "property" {
@@ -152,53 +1028,16 @@ impl TryFrom<&MetaList> for Property {
                 "excepted \"property\"",
             ));
         }
-        let nested_iter = &mut meta_list.nested.iter();
-        // Get the name of the property
-        let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
 
-        // Get the signature
-        let signature = get_lit_str_from_option_nested_meta(nested_iter.next())?;
-        let mut signature_iter = SignatureIterator::from(&signature);
-        let signature = if let Some(signature) = signature_iter.next() {
-            signature?
+        let (meta_list, annotations) = split_annotations(meta_list)?;
+        let (meta_list, doc) = split_doc(&meta_list)?;
+        let mut property = if is_named_syntax(&meta_list) {
+            Property::try_from_named(&meta_list)?
         } else {
-            return Err(SynError::new(
-                signature.span(),
-                "excepted only one signature type",
-            ));
+            Property::try_from_positional(&meta_list)?
         };
-
-        if signature_iter.next().is_some() {
-            return Err(SynError::new(
-                meta_list.span(),
-                "excepted only one signature type",
-            ));
-        }
-
-        if let Some(nested_meta) = nested_iter.next() {
-            create_property_code(nested_meta, &mut get, &mut set)?;
-        } else {
-            return Err(SynError::new(
-                meta_list.nested.span(),
-                "no \"get\" or \"set\" function defined",
-            ));
-        }
-
-        // Check if there is another attribute
-        if let Some(nested_meta) = nested_iter.next() {
-            // Create the code for the forth attribute
-            create_property_code(nested_meta, &mut get, &mut set)?;
-        }
-
-        if nested_iter.next().is_some() {
-            return Err(SynError::new(meta_list.span(), "too many arguments"));
-        }
-
-        Ok(Property {
-            name,
-            get,
-            set,
-            signature,
-        })
+        property.set_annotations(annotations)?;
+        property.doc = doc;
+        Ok(property)
     }
 }
@@ -1,24 +1,193 @@
+use crate::annotation::{annotations_to_introspect, split_annotations, Annotation};
+use crate::argument::{
+    check_argument_names, get_arguments_from_lit_str, get_arguments_from_nested_meta, Argument,
+};
+use crate::doc::{doc_to_introspect, split_doc};
 use crate::helper::{
-    get_ident_from_path, get_lit_str_from_option_nested_meta,
-    get_signatures_from_option_nested_meta,
+    get_default_emit_function_name, get_ident_from_lit, get_ident_from_path, get_lit_str_from_lit,
+    get_lit_str_from_option_nested_meta, get_meta_name_value_from_nested_meta, get_named_arguments,
+    get_required_argument, is_named_syntax,
 };
 use crate::introspectable::Introspectable;
-use proc_macro2::TokenStream;
+use crate::name::check_member_name;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
 use std::convert::TryFrom;
 use syn::spanned::Spanned;
-use syn::{Error as SynError, LitStr, MetaList};
+use syn::{Error as SynError, LitStr, MetaList, Result as SynResult};
 
 pub(crate) struct Signal {
     name: LitStr,
     signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+    arguments: Vec<Argument>,
+    /// The name of the emit function, which is chosen by `emit_fn`.
+    emit_function: Option<Ident>,
+    annotations: Vec<Annotation>,
+    doc: Option<LitStr>,
+}
+
+/// The names, which are used by the generated code and cannot be used for arguments.
+const RESERVED_NAMES: [&str; 4] = ["self", "dbus", "path", "msg"];
+
+impl Signal {
+    pub(super) fn new(
+        name: LitStr,
+        signatures: Vec<(String, TokenStream, TokenStream, TokenStream)>,
+        arguments: Vec<Argument>,
+    ) -> SynResult<Signal> {
+        check_member_name(&name, "signal")?;
+        check_argument_names(&arguments, 0, &RESERVED_NAMES)?;
+        Ok(Signal {
+            name,
+            signatures,
+            arguments,
+            emit_function: None,
+            annotations: Vec::new(),
+            doc: None,
+        })
+    }
+
+    pub(super) fn get_name(&self) -> &LitStr {
+        &self.name
+    }
+
+    pub(super) fn set_annotations(&mut self, annotations: Vec<Annotation>) {
+        self.annotations = annotations;
+    }
+
+    pub(super) fn set_doc(&mut self, doc: Option<LitStr>) {
+        self.doc = doc;
+    }
+
+    /// Get the name of the emit function, if it is chosen by `emit_fn`.
+    pub(super) fn get_emit_function(&self) -> Option<&Ident> {
+        self.emit_function.as_ref()
+    }
+
+    /// Get the name of the function, which emits the signal of the interface `interface`.
+    /// By default, the name is qualified by the interface.
+    fn get_emit_function_name(&self, interface: &LitStr) -> Ident {
+        if let Some(emit_function) = &self.emit_function {
+            emit_function.clone()
+        } else {
+            let function = get_default_emit_function_name(interface, &self.name);
+            Ident::new(&function, self.name.span())
+        }
+    }
+
+    /// Create the function, which emits the signal of the interface `interface`.
+    /// The arguments are converted by the code of the signature.
+    pub(super) fn create_emit_function_code(&self, interface: &LitStr) -> TokenStream {
+        let name = &self.name;
+        let function = self.get_emit_function_name(interface);
+        let doc = format!(
+            "Emit the signal `{}` of the interface `{}` from the object at `path`.",
+            name.value(),
+            interface.value()
+        );
+
+        let mut arguments = Vec::new();
+        let mut add_values = Vec::new();
+        for (i, ((_, rust_type, _, rust_to_value), argument)) in
+            self.signatures.iter().zip(&self.arguments).enumerate()
+        {
+            let argument = argument.get_ident(i, name.span());
+            arguments.push(quote! { #argument: #rust_type });
+            add_values.push(quote! {
+                let i: #rust_type = #argument;
+                let v: __dbus_message_parser::Value = #rust_to_value;
+                msg.add_value(v);
+            });
+        }
+
+        let mut_msg = if self.signatures.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! { mut }
+        };
+        quote! {
+            #[doc = #doc]
+            pub fn #function(
+                &self,
+                dbus: &__dbus_async::DBus,
+                path: &__dbus_message_parser::ObjectPath,
+                #(#arguments),*
+            ) -> __dbus_async::DBusResult<()> {
+                let #mut_msg msg = __dbus_message_parser::Message::signal(
+                    path.clone(),
+                    ::std::convert::TryFrom::try_from(#interface).unwrap(),
+                    ::std::convert::TryFrom::try_from(#name).unwrap(),
+                );
+                #({#add_values})*
+                dbus.send(msg)
+            }
+        }
+    }
+
+    /// Parse the named-key syntax: `signal(name = "Name", args = "signature", emit_fn = function)`.
+    fn try_from_named(meta_list: &MetaList) -> SynResult<Signal> {
+        let mut name = None;
+        let mut arguments = (Vec::new(), Vec::new());
+        let mut emit_function = None;
+        for (key, lit) in get_named_arguments(meta_list, &["name", "args", "emit_fn"])? {
+            match key.to_string().as_ref() {
+                "name" => name = Some(get_lit_str_from_lit(lit)?),
+                "args" => arguments = get_arguments_from_lit_str(&get_lit_str_from_lit(lit)?)?,
+                "emit_fn" => emit_function = Some(get_ident_from_lit(lit)?),
+                _ => unreachable!(),
+            }
+        }
+
+        let name = get_required_argument(meta_list, "name", name)?;
+        let mut signal = Signal::new(name, arguments.0, arguments.1)?;
+        signal.emit_function = emit_function;
+        Ok(signal)
+    }
+
+    /// Parse the positional syntax: `signal("Name", "signature")` or
+    /// `signal("Name", args(arg("name", "s"), ...))`. The name of the emit function can be chosen
+    /// by `emit_fn = function` after the signature.
+    fn try_from_positional(meta_list: &MetaList) -> SynResult<Signal> {
+        let nested_iter = &mut meta_list.nested.iter();
+
+        // Get the name of the signal
+        let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
+
+        let arguments = match nested_iter.next() {
+            Some(nested_meta) => get_arguments_from_nested_meta(nested_meta, "args")?,
+            None => return Err(SynError::new(meta_list.span(), "excepted a signature")),
+        };
+
+        let emit_function = if let Some(nested_meta) = nested_iter.next() {
+            let meta_name_value = get_meta_name_value_from_nested_meta(nested_meta)?;
+            let key = get_ident_from_path(&meta_name_value.path)?;
+            if key != "emit_fn" {
+                return Err(SynError::new(key.span(), "excepted \"emit_fn\""));
+            }
+            Some(get_ident_from_lit(&meta_name_value.lit)?)
+        } else {
+            None
+        };
+
+        if nested_iter.next().is_some() {
+            return Err(SynError::new(meta_list.span(), "too many arguments"));
+        }
+        let mut signal = Signal::new(name, arguments.0, arguments.1)?;
+        signal.emit_function = emit_function;
+        Ok(signal)
+    }
 }
 
 impl Introspectable for Signal {
     fn to_introspect(&self, xml: &mut String) {
+        doc_to_introspect(self.doc.as_ref(), xml, "    ");
         *xml += &format!("    <signal name=\"{}\">\n", self.name.value());
-        for (signature, _, _, _) in &self.signatures {
-            *xml += &format!("      <arg type=\"{}\"/>\n", signature);
+        for ((signature, _, _, _), argument) in self.signatures.iter().zip(&self.arguments) {
+            // Only named arguments have a name in the XML
+            let name = argument.get_name_if_named();
+            argument.to_introspect(xml, signature, name.as_deref(), None);
         }
+        annotations_to_introspect(&self.annotations, xml, "      ");
         *xml += "    </signal>\n";
     }
 }
@@ -33,17 +202,15 @@ impl TryFrom<&MetaList> for Signal {
             return Err(SynError::new(meta_list_type.span(), "excepted \"signal\""));
         }
 
-        let nested_iter = &mut meta_list.nested.iter();
-
-        // Get the name of the signal
-        let name = get_lit_str_from_option_nested_meta(nested_iter.next())?;
-
-        let mut signatures = Vec::new();
-        get_signatures_from_option_nested_meta(nested_iter.next(), &mut signatures)?;
-
-        if nested_iter.next().is_some() {
-            return Err(SynError::new(meta_list.span(), "too many arguments"));
-        }
-        Ok(Signal { name, signatures })
+        let (meta_list, annotations) = split_annotations(meta_list)?;
+        let (meta_list, doc) = split_doc(&meta_list)?;
+        let mut signal = if is_named_syntax(&meta_list) {
+            Signal::try_from_named(&meta_list)?
+        } else {
+            Signal::try_from_positional(&meta_list)?
+        };
+        signal.annotations = annotations;
+        signal.doc = doc;
+        Ok(signal)
     }
 }
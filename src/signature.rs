@@ -4,7 +4,10 @@ use crate::code::{
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Error as SynError, Index, LitStr, Result as SynResult};
+use syn::spanned::Spanned;
+use syn::{
+    Error as SynError, GenericArgument, Index, LitStr, PathArguments, Result as SynResult, Type,
+};
 
 /// Iterator for signature.
 /// This Iterator returns a single signature type and with the corresponding
@@ -26,6 +29,20 @@ impl From<&LitStr> for SignatureIterator {
 }
 
 impl SignatureIterator {
+    /// Create an error for the character at `offset` of the signature. The compiler does not
+    /// support spans inside of literals on stable, so the error points at the whole literal and
+    /// the character is marked by the offset and a caret in the message.
+    fn error(&self, offset: usize, message: &str) -> SynError {
+        let message = format!(
+            "{} at offset {}\n{}\n{}^",
+            message,
+            offset,
+            self.signature,
+            " ".repeat(offset)
+        );
+        SynError::new(self.span, message)
+    }
+
     fn basic(
         &mut self,
         value_type: &str,
@@ -62,10 +79,14 @@ impl SignatureIterator {
             'u' => self.basic("Uint32", "u32", "u"),
             'x' => self.basic("Int64", "i64", "x"),
             't' => self.basic("Uint64", "u64", "t"),
-            's' => self.basic("String", "String", "s"),
-            'o' => self.basic("ObjectPath", "String", "o"),
-            'g' => self.basic("Signature", "String", "g"),
-            'v' => self.basic("Variant", "std::boxed::Box<Value>", "v"),
+            's' => self.basic("String", "::std::string::String", "s"),
+            'o' => self.basic("ObjectPath", "::std::string::String", "o"),
+            'g' => self.basic("Signature", "::std::string::String", "g"),
+            'v' => self.basic(
+                "Variant",
+                "::std::boxed::Box<__dbus_message_parser::Value>",
+                "v",
+            ),
             'a' => {
                 let start_offset = self.offset;
                 // It is an array
@@ -82,12 +103,12 @@ impl SignatureIterator {
                     let signature = &self.signature[start_offset..self.offset];
                     let check_signature = check_signature(&inner_signature);
                     let default_case_wrong_case = default_case_wrong_case(signature);
-                    let rust_type = quote! { Vec<#inner_rust_type>};
+                    let rust_type = quote! { ::std::vec::Vec<#inner_rust_type>};
                     let value_to_rust = quote! {
                         match i {
-                            dbus_message_parser::Value::Array(i, signature) => {
+                            __dbus_message_parser::Value::Array(i, signature) => {
                                 #check_signature;
-                                let mut o = Vec::new();
+                                let mut o = ::std::vec::Vec::new();
                                 for i in i {
                                     o.push(#inner_value_to_rust);
                                 }
@@ -98,17 +119,17 @@ impl SignatureIterator {
                     };
                     let rust_to_value = quote! {
                         {
-                            let mut o = Vec::new();
+                            let mut o = ::std::vec::Vec::new();
                             for i in i {
                                 o.push(#inner_rust_to_value)
                             }
-                            dbus_message_parser::Value::Array(o, #inner_signature.to_string())
+                            __dbus_message_parser::Value::Array(o, #inner_signature.to_string())
                         }
                     };
 
                     Ok(Some((signature, rust_type, value_to_rust, rust_to_value)))
                 } else {
-                    Err(SynError::new(self.span, "Array was the last character"))
+                    Err(self.error(start_offset, "Array was the last character"))
                 }
             }
             '(' => {
@@ -125,13 +146,13 @@ impl SignatureIterator {
                             let signature = &self.signature[start_offset..self.offset];
                             rust_type += ")";
                             if rust_type == "()" {
-                                return Err(SynError::new(self.span, "struct is empty"));
+                                return Err(self.error(start_offset, "struct is empty"));
                             } else {
                                 let default_case_wrong_case = default_case_wrong_case(signature);
                                 let rust_type = rust_type.parse().unwrap();
                                 let value_to_rust = quote! {
                                     match i {
-                                        dbus_message_parser::Value::Struct(i) => {
+                                        __dbus_message_parser::Value::Struct(i) => {
                                             let mut i_iter = i.into_iter();
                                             #(#vec_inner_value_to_rust)*
                                             (#(#vec_inner_value_to_rust_return),*)
@@ -142,9 +163,9 @@ impl SignatureIterator {
                                 let rust_to_value = quote! {
                                     {
                                         let i_tuple = i;
-                                        let mut o = Vec::new();
+                                        let mut o = ::std::vec::Vec::new();
                                         #(#vec_inner_rust_to_value)*
-                                        dbus_message_parser::Value::Struct(o)
+                                        __dbus_message_parser::Value::Struct(o)
                                     }
                                 };
                                 return Ok(Some((
@@ -170,7 +191,7 @@ impl SignatureIterator {
                             );
                             let missing_value = missing_value(&inner_signature);
                             let inner_rust_type_conv = quote! {
-                                let #o = if let Some(i) = i_iter.next() {
+                                let #o = if let ::std::option::Option::Some(i) = i_iter.next() {
                                     #inner_value_to_rust
                                 } else {
                                     #missing_value
@@ -187,10 +208,10 @@ impl SignatureIterator {
                             };
                             vec_inner_rust_to_value.push(inner_enum_type_conv);
                         } else {
-                            return Err(SynError::new(self.span, ") was not closed"));
+                            return Err(self.error(start_offset, ") was not closed"));
                         }
                     } else {
-                        return Err(SynError::new(self.span, ") was not closed"));
+                        return Err(self.error(start_offset, ") was not closed"));
                     }
                 }
             }
@@ -210,7 +231,7 @@ impl SignatureIterator {
                                 let rust_type = rust_type.parse().unwrap();
                                 let value_to_rust = quote! {
                                     match i {
-                                        dbus_message_parser::Value::DictEntry(i_entry) => {
+                                        __dbus_message_parser::Value::DictEntry(i_entry) => {
                                             let i_entry = *i_entry;
 
                                             let i = i_entry.0;
@@ -234,27 +255,26 @@ impl SignatureIterator {
                                         let i = entry.1;
                                         let value = #value_rust_to_value;
 
-                                        dbus_message_parser::Value::DictEntry(Box::new((key, value)))
+                                        __dbus_message_parser::Value::DictEntry(::std::boxed::Box::new((key, value)))
                                     }
                                 };
                                 Ok(Some((signature, rust_type, value_to_rust, rust_to_value)))
                             } else {
-                                Err(SynError::new(self.span, "} was not closed"))
+                                Err(self.error(start_offset, "} was not closed"))
                             }
                         } else {
-                            Err(SynError::new(self.span, "} was not closed"))
+                            Err(self.error(start_offset, "} was not closed"))
                         }
                     } else {
-                        Err(SynError::new(self.span, "Could not get value type"))
+                        Err(self.error(start_offset, "Could not get value type"))
                     }
                 } else {
-                    Err(SynError::new(self.span, "Could not get key type"))
+                    Err(self.error(start_offset, "Could not get key type"))
                 }
             }
-            unknown_char => Err(SynError::new(
-                self.span,
-                format!("unknown signature: {}", unknown_char),
-            )),
+            unknown_char => {
+                Err(self.error(self.offset, &format!("unknown signature: {}", unknown_char)))
+            }
         }
     }
 }
@@ -265,16 +285,174 @@ impl Iterator for SignatureIterator {
     fn next(&mut self) -> Option<Self::Item> {
         // Get the next signature
         match self.get_next() {
-            Ok(r) => match r {
-                Some((signature, rust_type, value_to_rust, rust_to_value)) => Some(Ok((
+            Ok(r) => r.map(|(signature, rust_type, value_to_rust, rust_to_value)| {
+                Ok((
                     signature.to_string(),
                     rust_type,
                     value_to_rust,
                     rust_to_value,
-                ))),
-                None => None,
-            },
+                ))
+            }),
             Err(e) => Some(Err(e)),
         }
     }
 }
+
+/// Get the only generic type argument of a path segment, for example `T` of `Vec<T>`.
+fn get_generic_type(arguments: &PathArguments) -> Option<&Type> {
+    if let PathArguments::AngleBracketed(arguments) = arguments {
+        if arguments.args.len() == 1 {
+            if let Some(GenericArgument::Type(rust_type)) = arguments.args.first() {
+                return Some(rust_type);
+            }
+        }
+    }
+    None
+}
+
+/// Infer the DBus signature of a Rust type.
+/// This is the inverse of the conversion, which is done by the `SignatureIterator`.
+pub(super) fn signature_from_type(rust_type: &Type) -> SynResult<String> {
+    match rust_type {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            if let Some(segment) = type_path.path.segments.last() {
+                let ident = segment.ident.to_string();
+                match ident.as_str() {
+                    "u8" => return Ok("y".to_string()),
+                    "bool" => return Ok("b".to_string()),
+                    "i16" => return Ok("n".to_string()),
+                    "u16" => return Ok("q".to_string()),
+                    "i32" => return Ok("i".to_string()),
+                    "u32" => return Ok("u".to_string()),
+                    "i64" => return Ok("x".to_string()),
+                    "u64" => return Ok("t".to_string()),
+                    "String" => return Ok("s".to_string()),
+                    "Vec" => {
+                        if let Some(inner_rust_type) = get_generic_type(&segment.arguments) {
+                            let inner_signature = signature_from_type(inner_rust_type)?;
+                            return Ok(format!("a{}", inner_signature));
+                        }
+                    }
+                    "Box" => {
+                        if let Some(Type::Path(inner_type_path)) =
+                            get_generic_type(&segment.arguments)
+                        {
+                            if let Some(segment) = inner_type_path.path.segments.last() {
+                                if segment.ident == "Value" {
+                                    return Ok("v".to_string());
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Type::Tuple(type_tuple) if !type_tuple.elems.is_empty() => {
+            let mut signature = "(".to_string();
+            for elem in &type_tuple.elems {
+                signature += &signature_from_type(elem)?;
+            }
+            signature += ")";
+            return Ok(signature);
+        }
+        Type::Paren(type_paren) => return signature_from_type(&type_paren.elem),
+        Type::Group(type_group) => return signature_from_type(&type_group.elem),
+        _ => {}
+    }
+    Err(SynError::new(
+        rust_type.span(),
+        "could not infer the DBus signature of this type, define the signature explicitly",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{signature_from_type, SignatureIterator};
+    use proc_macro2::Span;
+    use syn::{parse_str, LitStr, Type};
+
+    fn signature(rust_type: &str) -> Result<String, String> {
+        let rust_type: Type = parse_str(rust_type).unwrap();
+        signature_from_type(&rust_type).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn basic_types() {
+        assert_eq!(signature("u8").unwrap(), "y");
+        assert_eq!(signature("bool").unwrap(), "b");
+        assert_eq!(signature("i16").unwrap(), "n");
+        assert_eq!(signature("u16").unwrap(), "q");
+        assert_eq!(signature("i32").unwrap(), "i");
+        assert_eq!(signature("u32").unwrap(), "u");
+        assert_eq!(signature("i64").unwrap(), "x");
+        assert_eq!(signature("u64").unwrap(), "t");
+        assert_eq!(signature("String").unwrap(), "s");
+        assert_eq!(signature("std::string::String").unwrap(), "s");
+    }
+
+    #[test]
+    fn container_types() {
+        assert_eq!(signature("Vec<u32>").unwrap(), "au");
+        assert_eq!(signature("Vec<Vec<String>>").unwrap(), "aas");
+        assert_eq!(signature("(u32, String)").unwrap(), "(us)");
+        assert_eq!(signature("Vec<(u8, (bool, i64))>").unwrap(), "a(y(bx))");
+        assert_eq!(signature("Box<Value>").unwrap(), "v");
+        assert_eq!(signature("(u32)").unwrap(), "u");
+    }
+
+    #[test]
+    fn unknown_types() {
+        let error =
+            "could not infer the DBus signature of this type, define the signature explicitly";
+        assert_eq!(signature("f32").unwrap_err(), error);
+        assert_eq!(signature("()").unwrap_err(), error);
+        assert_eq!(signature("Vec<f32>").unwrap_err(), error);
+        assert_eq!(signature("Box<u32>").unwrap_err(), error);
+        assert_eq!(signature("&str").unwrap_err(), error);
+    }
+
+    fn signatures(signature: &str) -> Result<Vec<String>, String> {
+        let signature = LitStr::new(signature, Span::call_site());
+        SignatureIterator::from(&signature)
+            .map(|result| {
+                result
+                    .map(|(signature, _, _, _)| signature)
+                    .map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn single_signatures() {
+        assert_eq!(
+            signatures("sa{sv}(ub)aay").unwrap(),
+            vec!["s", "a{sv}", "(ub)", "aay"]
+        );
+        assert!(signatures("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn signature_errors() {
+        assert_eq!(
+            signatures("a{sx").unwrap_err(),
+            "} was not closed at offset 1\na{sx\n ^"
+        );
+        assert_eq!(
+            signatures("s(ub").unwrap_err(),
+            ") was not closed at offset 1\ns(ub\n ^"
+        );
+        assert_eq!(
+            signatures("ua").unwrap_err(),
+            "Array was the last character at offset 1\nua\n ^"
+        );
+        assert_eq!(
+            signatures("()").unwrap_err(),
+            "struct is empty at offset 0\n()\n^"
+        );
+        assert_eq!(
+            signatures("sz").unwrap_err(),
+            "unknown signature: z at offset 1\nsz\n ^"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use roxmltree::{Document, Node, ParsingOptions};
+
+/// An element of a XML document. The text between the elements is ignored.
+pub(super) struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    /// The comment in front of the element.
+    doc: Option<String>,
+    /// The line of the start tag.
+    line: usize,
+}
+
+impl Element {
+    pub(super) fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(super) fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(super) fn get_children(&self) -> &[Element] {
+        &self.children
+    }
+
+    pub(super) fn get_doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub(super) fn get_line(&self) -> usize {
+        self.line
+    }
+}
+
+/// Get the comment in front of the node as documentation. A comment only documents the following
+/// element, if there is no other element or text between them.
+fn get_doc(node: Node) -> Option<String> {
+    let mut previous = node.prev_sibling();
+    while let Some(sibling) = previous {
+        if sibling.is_comment() {
+            let comment = sibling.text().unwrap_or_default();
+            let lines: Vec<&str> = comment.lines().map(str::trim).collect();
+            return Some(lines.join("\n").trim().to_string());
+        }
+        if sibling.is_element()
+            || (sibling.is_text() && !sibling.text().unwrap_or_default().trim().is_empty())
+        {
+            return None;
+        }
+        previous = sibling.prev_sibling();
+    }
+    None
+}
+
+/// Convert the element `node` and its children. The elements and the attributes of other
+/// namespaces (e.g. `<doc:doc>`) are not part of the introspection data, so they are skipped.
+fn create_element(document: &Document, node: Node) -> Element {
+    let attributes = node
+        .attributes()
+        .filter(|attribute| attribute.namespace().is_none())
+        .map(|attribute| (attribute.name().to_string(), attribute.value().to_string()))
+        .collect();
+    let children = node
+        .children()
+        .filter(|child| child.is_element() && child.tag_name().namespace().is_none())
+        .map(|child| create_element(document, child))
+        .collect();
+    Element {
+        name: node.tag_name().name().to_string(),
+        attributes,
+        children,
+        doc: get_doc(node),
+        line: document.text_pos_at(node.range().start).row as usize,
+    }
+}
+
+/// Parse a XML document and return the root element. The document type declaration of the
+/// introspection data is allowed.
+pub(super) fn parse_xml(xml: &str) -> Result<Element, String> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(xml, options).map_err(|e| e.to_string())?;
+    Ok(create_element(&document, document.root_element()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_xml;
+
+    #[test]
+    fn doc_of_documented_and_undocumented_elements() {
+        let xml = r#"<interface name="org.example.interface">
+  <!-- Doc for First -->
+  <method name="First"/>
+  <method name="Second"/>
+  <!--
+    Doc for
+    Third
+  -->
+  <method name="Third"/>
+</interface>"#;
+        let root = parse_xml(xml).unwrap();
+        let docs: Vec<Option<&str>> = root.get_children().iter().map(|e| e.get_doc()).collect();
+        assert_eq!(
+            docs,
+            vec![Some("Doc for First"), None, Some("Doc for\nThird")]
+        );
+    }
+
+    #[test]
+    fn doc_is_reset_by_text() {
+        let xml = r#"<node><!-- Comment -->text<interface name="a.b"/></node>"#;
+        let root = parse_xml(xml).unwrap();
+        assert_eq!(root.get_children()[0].get_doc(), None);
+    }
+
+    #[test]
+    fn attributes_children_and_lines() {
+        let xml =
+            "<node>\n  <interface name=\"a.b\">\n    <method name=\"M\"/>\n  </interface>\n</node>";
+        let root = parse_xml(xml).unwrap();
+        assert_eq!(root.get_name(), "node");
+        assert_eq!(root.get_line(), 1);
+        let interface = &root.get_children()[0];
+        assert_eq!(interface.get_name(), "interface");
+        assert_eq!(interface.get_attribute("name"), Some("a.b"));
+        assert_eq!(interface.get_attribute("other"), None);
+        assert_eq!(interface.get_line(), 2);
+        assert_eq!(interface.get_children()[0].get_line(), 3);
+    }
+
+    #[test]
+    fn doctype_and_escaped_attributes() {
+        let xml = r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node><annotation name="a.b" value="&lt;&amp;&quot;&gt;"/></node>"#;
+        let root = parse_xml(xml).unwrap();
+        let annotation = &root.get_children()[0];
+        assert_eq!(annotation.get_attribute("value"), Some("<&\">"));
+    }
+
+    #[test]
+    fn namespaced_elements_and_attributes_are_skipped() {
+        let xml = r#"<node xmlns:doc="http://www.freedesktop.org/dbus/1.0/doc.dtd">
+  <interface name="a.b" doc:x="y"><doc:doc><doc:summary>S</doc:summary></doc:doc></interface>
+</node>"#;
+        let root = parse_xml(xml).unwrap();
+        let interface = &root.get_children()[0];
+        assert_eq!(interface.get_attribute("x"), None);
+        assert!(interface.get_children().is_empty());
+    }
+
+    #[test]
+    fn invalid_xml() {
+        assert!(parse_xml("<node><interface></node>").is_err());
+        assert!(parse_xml("").is_err());
+    }
+}
@@ -0,0 +1,8 @@
+/// Check the error messages of the derive macro. The expected messages are stored in the
+/// `.stderr` files next to the test cases. They can be updated by running the tests with
+/// `TRYBUILD=overwrite`.
+#[test]
+fn compile_fail() {
+    let test_cases = trybuild::TestCases::new();
+    test_cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,38 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Method", method, "s:name u:name"))]
+struct DuplicateName {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Method", method, "s:dbus"))]
+struct ReservedName {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Method", method, "su:name"))]
+struct MultipleTypes {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", signal("Signal", "s:1name"))]
+struct InvalidName {}
+
+#[derive(Handler)]
+#[interface(
+    "org.example.interface",
+    method(
+        "Method",
+        method,
+        args(arg("text", "s"), arg("text", "u")),
+        returns(arg("result", "s"))
+    )
+)]
+struct DuplicateArg {}
+
+#[derive(Handler)]
+#[interface(
+    "org.example.interface",
+    method("Method", method, args(arg("text", "s"), "u"))
+)]
+struct MixedArgs {}
+
+fn main() {}
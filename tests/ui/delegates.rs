@@ -0,0 +1,42 @@
+use dbus_async::DBus;
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+
+#[derive(Handler)]
+#[interface("org.example.Lifecycle", method("Restart", restart))]
+struct LifecycleImpl {}
+
+impl LifecycleImpl {
+    async fn restart(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        Ok(())
+    }
+}
+
+#[derive(Handler)]
+#[interface("org.example.Lifecycle", method("Restart", restart))]
+struct SameInterface {
+    #[delegate]
+    lifecycle: LifecycleImpl,
+}
+
+impl SameInterface {
+    async fn restart(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<(), (Error, String)> {
+        Ok(())
+    }
+}
+
+#[derive(Handler)]
+struct GenericDelegate<T: Send + Sync> {
+    #[delegate]
+    inner: Vec<T>,
+}
+
+fn main() {}
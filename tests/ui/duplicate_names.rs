@@ -0,0 +1,24 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface(
+    "org.example.interface",
+    method("Method", method),
+    method("Method", other_method)
+)]
+struct DuplicateMethod {}
+
+#[derive(Handler)]
+#[interface(
+    "org.example.interface",
+    property("Property", "u", get_property = "get"),
+    property("Property", "s", get_other_property = "get")
+)]
+struct DuplicateProperty {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Method", method))]
+#[interface("org.example.interface", method("Other", other_method))]
+struct DuplicateInterface {}
+
+fn main() {}
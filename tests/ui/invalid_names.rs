@@ -0,0 +1,23 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface("org..example", method("Method", method))]
+struct EmptyElement {}
+
+#[derive(Handler)]
+#[interface("1abc.example", method("Method", method))]
+struct LeadingDigit {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Get.All", method))]
+struct MemberWithDot {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", property("My-Property", "u", get_property = "get"))]
+struct InvalidProperty {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", signal("1Signal", "s"))]
+struct InvalidSignal {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dbus_async_derive::Handler;
+
+/// Other attributes, like the documentation, are ignored.
+#[derive(Handler)]
+#[interfac("org.example.interface")]
+struct MisspelledInterface {}
+
+fn main() {}
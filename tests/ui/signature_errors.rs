@@ -0,0 +1,11 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface("org.example.interface", method("Method", method, "a{sx"))]
+struct NotClosed {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", signal("Signal", "sz"))]
+struct UnknownSignature {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface("org.freedesktop.DBus.Properties", method("Get", get))]
+struct PropertiesCollision {}
+
+#[derive(Handler)]
+#[interface("org.freedesktop.DBus.Introspectable", method("Introspect", introspect))]
+struct IntrospectableCollision {}
+
+#[derive(Handler)]
+#[peer(true)]
+#[interface("org.freedesktop.DBus.Peer", method("Ping", ping))]
+struct PeerCollision {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", replace, method("Method", method))]
+struct ReplaceNonStandard {}
+
+fn main() {}
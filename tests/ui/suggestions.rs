@@ -0,0 +1,23 @@
+use dbus_async_derive::Handler;
+
+#[derive(Handler)]
+#[interface("org.example.interface", propery("Property", "u", get_property = "get"))]
+struct MisspelledProperty {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", property("Property", "u", get_property = "gte"))]
+struct MisspelledGet {}
+
+#[derive(Handler)]
+#[interface("org.freedesktop.DBus.Peer", replac, method("Ping", ping))]
+struct MisspelledReplace {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", get_al = "skip", method("Method", method))]
+struct MisspelledGetAll {}
+
+#[derive(Handler)]
+#[interface("org.example.interface", method(name = "Method", handlr = method))]
+struct MisspelledKey {}
+
+fn main() {}
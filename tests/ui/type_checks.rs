@@ -0,0 +1,44 @@
+use dbus_async::DBus;
+use dbus_async_derive::Handler;
+use dbus_message_parser::{Error, MessageHeader};
+
+#[derive(Handler)]
+#[interface(
+    "org.example.interface",
+    method("ExampleMethod", method, "s", "u"),
+    property("ExampleProperty", "s", get_property = "get", set_property = "set"),
+    property("FieldProperty", "u", field = counter, access = "read")
+)]
+struct DBusObject {
+    counter: String,
+}
+
+impl DBusObject {
+    async fn method(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        _value: u32,
+    ) -> Result<String, (Error, String)> {
+        Ok(String::new())
+    }
+
+    async fn get_property(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+    ) -> Result<bool, (Error, String)> {
+        Ok(true)
+    }
+
+    async fn set_property(
+        &mut self,
+        _dbus: &DBus,
+        _msg_header: &MessageHeader,
+        _value: i64,
+    ) -> Result<(), (Error, String)> {
+        Ok(())
+    }
+}
+
+fn main() {}